@@ -1,4 +1,4 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #![warn(
     clippy::complexity,
     clippy::correctness,
@@ -9,40 +9,75 @@
 )]
 
 use asr::{
-    Address, Process,
+    Address, PointerSize, Process,
     file_format::pe,
     future::{next_tick, retry},
-    settings::Gui,
+    settings::{Gui, gui::Title},
     string::ArrayCString,
     time::Duration,
+    time_util::Instant,
     timer::{self, TimerState},
-    watcher::Watcher,
+    watcher::{Pair, Watcher},
 };
 
+#[cfg(not(test))]
 asr::async_main!(stable);
+#[cfg(not(test))]
 asr::panic_handler!();
 
-const PROCESS_NAMES: &[&str] = &["SniperEliteV2.exe", "SEV2_Remastered.exe"];
+/// Executable names to attach to, in order of preference.
+///
+/// Attaching compares names exactly and the runtime can't list every process to compare
+/// them in any other way, so the lowercase spellings used by some re-packs are listed
+/// separately. Everything else compares the names case-insensitively.
+const PROCESS_NAMES: &[&str] = &[
+    "SniperEliteV2.exe",
+    "SEV2_Remastered.exe",
+    "sniperelitev2.exe",
+    "sev2_remastered.exe",
+];
+/// Executable names of wrappers and ports running the game under a different name
+/// and module layout. None are known yet, and any that get added are recognized
+/// as [`GameVersion::Other`] until offsets for them are contributed.
+const PORT_PROCESS_NAMES: &[&str] = &[];
+const OG_PROCESS_NAME: &str = PROCESS_NAMES[0];
+const REMASTERED_PROCESS_NAME: &str = PROCESS_NAMES[1];
 
+#[cfg_attr(test, allow(dead_code))]
 async fn main() {
     let mut settings = Settings::register();
+    // The progress of the run outlives the process, so a game that gets relaunched
+    // mid-run picks up where it left off
+    let mut run = RunState::default();
 
     loop {
+        timer::set_variable("Status", "Searching for game");
+
         // Hook to the target process
         let (process_name, process) = retry(|| {
             PROCESS_NAMES
                 .iter()
+                .chain(PORT_PROCESS_NAMES)
                 .find_map(|&name| Some((name, Process::attach(name)?)))
         })
         .await;
 
+        asr::print_limited::<64>(&format_args!("Attached to {process_name}"));
+
         process
             .until_closes(async {
-                // Once the target has been found and attached to, set up some default watchers
-                let mut watchers = Watchers::default();
+                // Once the target has been found and attached to, set up some default watchers.
+                // This also happens when re-attaching to a game that got relaunched mid-run.
+                let mut watchers = Watchers {
+                    run,
+                    ..Default::default()
+                };
 
                 // Perform memory scanning to look for the addresses we need
-                let addresses = Memory::init(&process, process_name).await;
+                let (mut addresses, version) =
+                    resolve_addresses(&process, process_name, &mut settings).await;
+                timer::set_variable("Status", version.status());
+                watchers.build = Some((addresses.main_module_size, addresses.offset_table));
 
                 loop {
                     // Splitting logic. Adapted from OG LiveSplit:
@@ -53,49 +88,32 @@ async fn main() {
                     // 4. If the timer is currently not running (and not paused), then the start action will be run.
                     settings.update();
 
-                    if watchers.slow_pc_mode != settings.slow_pc_mode {
-                        asr::set_tick_rate(match settings.slow_pc_mode {
-                            true => 60.0,
-                            false => 120.0,
-                        });
+                    update_loop(&process, &addresses, &mut watchers, &settings);
+                    let elapsed = tick_duration(&mut watchers);
 
-                        watchers.slow_pc_mode = settings.slow_pc_mode;
+                    let tick_rate = tick_rate(&watchers, &settings);
+                    if watchers.tick_rate != tick_rate {
+                        asr::set_tick_rate(tick_rate);
+                        watchers.tick_rate = tick_rate;
                     }
 
-                    update_loop(&process, &addresses, &mut watchers);
-
-                    if [TimerState::Running, TimerState::Paused].contains(&timer::state()) {
-                        match is_loading(&watchers, &settings) {
-                            Some(true) => timer::pause_game_time(),
-                            Some(false) => timer::resume_game_time(),
-                            _ => (),
-                        }
-
-                        match game_time(&watchers, &settings, &addresses) {
-                            Some(x) => timer::set_game_time(x),
-                            _ => (),
-                        }
-
-                        match reset(&watchers, &settings) {
-                            true => timer::reset(),
-                            _ => match split(&watchers, &settings) {
-                                true => timer::split(),
-                                _ => (),
-                            },
-                        }
+                    // The addresses are gone for good if nothing can be read for a while,
+                    // which can happen when the game gets patched while it's running
+                    if reads_failing(&mut LiveSplit, &mut watchers, &settings) {
+                        timer::set_variable("Status", "Reads failing");
+                        let version;
+                        (addresses, version) =
+                            resolve_addresses(&process, process_name, &mut settings).await;
+                        timer::set_variable("Status", version.status());
+                        watchers.build = Some((addresses.main_module_size, addresses.offset_table));
+                        watchers.build_sent = false;
+                        watchers.failed_ticks = 0;
                     }
 
-                    if timer::state().eq(&TimerState::NotRunning) && start(&watchers, &settings) {
-                        timer::start();
-                        timer::pause_game_time();
-
-                        match is_loading(&watchers, &settings) {
-                            Some(true) => timer::pause_game_time(),
-                            Some(false) => timer::resume_game_time(),
-                            _ => (),
-                        }
-                    }
+                    update_variables(&mut watchers, &settings);
+                    update_timer(&mut LiveSplit, &mut watchers, &settings, elapsed);
 
+                    run = watchers.run;
                     next_tick().await;
                 }
             })
@@ -103,61 +121,1007 @@ async fn main() {
     }
 }
 
+/// The timer driven by the splitting logic, which is LiveSplit's in the auto splitter
+/// and a stand-in recording everything asked of it in the tests.
+trait Timer {
+    fn state(&self) -> TimerState;
+    fn start(&mut self);
+    /// Splits the current segment, as the given kind of split
+    fn split(&mut self, kind: SplitKind);
+    fn reset(&mut self);
+    fn pause_game_time(&mut self);
+    fn resume_game_time(&mut self);
+    fn set_game_time(&mut self, time: Duration);
+}
+
+/// The timer of the runtime the auto splitter runs in.
+struct LiveSplit;
+
+impl Timer for LiveSplit {
+    fn state(&self) -> TimerState {
+        timer::state()
+    }
+
+    fn start(&mut self) {
+        timer::start();
+    }
+
+    fn split(&mut self, _kind: SplitKind) {
+        timer::split();
+    }
+
+    fn reset(&mut self) {
+        timer::reset();
+    }
+
+    fn pause_game_time(&mut self) {
+        timer::pause_game_time();
+    }
+
+    fn resume_game_time(&mut self) {
+        timer::resume_game_time();
+    }
+
+    fn set_game_time(&mut self, time: Duration) {
+        timer::set_game_time(time);
+    }
+}
+
+/// Whether every read has been failing for long enough for the addresses to be
+/// rescanned, resetting the run first if the settings ask for it.
+///
+/// asr can't tell whether the game has focus, so a game that got minimized or
+/// alt-tabbed out of gets the same 5 seconds to answer again. Failed reads keep
+/// the previous values meanwhile, so load removal doesn't flip while the game is
+/// in the background.
+fn reads_failing(timer: &mut impl Timer, watchers: &mut Watchers, settings: &Settings) -> bool {
+    if (watchers.failed_ticks as f64) < watchers.tick_rate * 5.0 {
+        return false;
+    }
+
+    asr::print_message("Every read has been failing, rescanning the addresses");
+    if settings.reset_on_dead_reads {
+        safety_reset(timer, watchers, "every read has been failing");
+    }
+    true
+}
+
+/// Runs the start, split, reset and load removal logic for the values read on the
+/// current tick, which took `elapsed`.
+fn update_timer(
+    timer: &mut impl Timer,
+    watchers: &mut Watchers,
+    settings: &Settings,
+    elapsed: Duration,
+) {
+    // Nothing can be decided until every value has been read at least once
+    let Some(state) = GameState::new(watchers) else {
+        watchers.unpaired_ticks = watchers.unpaired_ticks.saturating_add(1);
+        if settings.reset_on_dead_reads
+            && watchers.unpaired_ticks as f64 >= watchers.tick_rate * 10.0
+        {
+            safety_reset(timer, watchers, "nothing has been read since attaching");
+            watchers.unpaired_ticks = 0;
+        }
+        return;
+    };
+    watchers.unpaired_ticks = 0;
+
+    let mission_end = confirm_mission_end(watchers, &state, settings);
+
+    // Timer states:
+    // - NotRunning: only the start action runs.
+    // - Running: load removal, game time, reset and split all run.
+    // - Paused: the timer only gets paused by the runner, as load removal
+    //   pauses game time rather than the timer. Load removal, game time and
+    //   reset keep running so nothing is off once the timer gets resumed,
+    //   but splits are held back until then.
+    // - Ended: nothing runs until the runner resets.
+    if [TimerState::Running, TimerState::Paused].contains(&timer.state()) {
+        // Game time left paused by an undecided start gets resumed
+        // on the next tick, unless a load is known to be going on
+        let loading = is_loading(&state, settings);
+        let loading = match settings.debounce_loads {
+            true => watchers.load_debounce.update(loading, watchers.tick_rate),
+            false => loading,
+        };
+        match loading {
+            Some(true) => pause_game_time(timer, watchers, settings),
+            Some(false) => resume_game_time(timer, watchers, settings),
+            None if watchers.resume_pending => resume_game_time(timer, watchers, settings),
+            _ => (),
+        }
+        watchers.resume_pending = false;
+
+        // IL runs start inside the mission's opening load, and game time has to
+        // run as soon as it's over, whether or not it got debounced. The debounce
+        // is caught up with it, or it would pause game time again until then.
+        if watchers.run.opening_load && state.load_byte.changed_from_to(&1, &0) {
+            watchers.run.opening_load = false;
+            if is_loading(&state, settings) != Some(true) {
+                watchers.load_debounce = LoadDebounce {
+                    state: Some(false),
+                    pending_ticks: 0,
+                };
+                resume_game_time(timer, watchers, settings);
+            }
+        }
+
+        let game_time = game_time(&state, settings);
+        if let Some(x) = game_time
+            && settings.game_time_update == GameTimeUpdate::Live
+        {
+            timer.set_game_time(x);
+        }
+
+        if reset(&state, settings) {
+            log_decision("Reset", &state, settings);
+            timer.reset();
+            clear_run(watchers);
+        } else if timer.state() != TimerState::Paused
+            && let Some((kind, then)) = confirm_split(watchers, &state, settings)
+        {
+            log_decision("Split", &then, settings);
+            let update = match settings.game_time_update {
+                GameTimeUpdate::Live => false,
+                GameTimeUpdate::OnSplit => true,
+                GameTimeUpdate::FinalOnly => kind.is_final(),
+            };
+            if let Some(x) = game_time
+                && update
+            {
+                timer.set_game_time(x);
+            }
+            timer.split(kind);
+            watchers.run.latch_split(&then, kind);
+            if kind.is_final() {
+                pause_game_time(timer, watchers, settings);
+            }
+            if kind == SplitKind::FinalKill
+                && let Some(x) = game_time
+                && settings.single_mission().is_none()
+            {
+                record_mission_igt(watchers, FINALE_INDEX, x);
+            }
+        }
+
+        if let Some(then) = &mission_end {
+            record_completed_mission_igt(watchers, then, game_time, settings);
+        }
+        watchers.run.track_boundary(&state, mission_end.as_ref());
+
+        let counted = timer.state() == TimerState::Running;
+        watchers.run.count_tick(&state, settings, counted, elapsed);
+    }
+
+    // The start offset holds the start back, counted in ticks at the current tick rate
+    match timer.state() {
+        TimerState::NotRunning => {
+            // The runner may have reset the timer by hand, which nothing else picks up
+            // on, so a stopped timer never has any progress left over from the last run
+            clear_run(watchers);
+
+            if watchers.pending_start.is_none() && start(&state, settings) {
+                log_decision("Start", &state, settings);
+                watchers.pending_start = Some(0);
+            }
+
+            if let Some(ticks) = watchers.pending_start {
+                match ticks as f64 >= settings.start_offset.seconds() * watchers.tick_rate {
+                    true => {
+                        watchers.pending_start = None;
+                        start_run(timer, watchers, &state, settings);
+                    }
+                    false => watchers.pending_start = Some(ticks + 1),
+                }
+            }
+        }
+        _ => watchers.pending_start = None,
+    }
+
+    track_prologue(watchers, &state, mission_end.is_some());
+}
+
+/// Every setting gets stored under its field name, so fields never get renamed once
+/// released, or the saved value would be lost. The keys are listed in the README, as
+/// the way to share a category's configuration: there's no single-string export, as
+/// one can't be encoded without an allocator.
 #[derive(Gui)]
 struct Settings {
+    /// General
+    _general: Title,
+    /// Start condition
+    start_mode: StartMode,
+    /// Start offset (delays the start by a fixed amount of time)
+    start_offset: StartOffset,
+    /// Reset when returning to the main menu
+    #[default = true]
+    reset_on_main_menu: bool,
+    /// Quitting to the main menu
+    ///
+    /// Nothing leaves it up to the reset and menu time settings, Reset always resets,
+    /// and Pause pauses game time instead of resetting, until a mission gets loaded
+    /// again. Loading it doesn't split, as it doesn't complete the mission left.
+    menu_behavior: MenuBehavior,
+    /// Reset when starting a New Game during a run
+    #[default = true]
+    reset_on_new_game: bool,
+    /// Main menu reset grace period after a split
+    reset_grace: ResetGrace,
+    /// Load removal
+    ///
+    /// When disabled, game time follows real time for RTA categories, and the timing
+    /// method has no effect.
+    #[default = true]
+    load_removal: bool,
+    /// Timing method
+    ///
+    /// Load removal pauses and resumes game time, leaving LiveSplit to count the rest.
+    /// Computed counts the time outside of loads here and sends it as game time,
+    /// whatever the frontend does with pausing.
+    timing_method: TimingMethod,
+    /// In-game time updates
+    ///
+    /// How often the game time computed here gets sent to LiveSplit, so it only applies
+    /// to the computed timing method. Load removal leaves it to LiveSplit entirely.
+    game_time_update: GameTimeUpdate,
+    /// Remove time spent in the main menu
+    #[default = true]
+    remove_menu_time: bool,
+    /// Full Game Splits
+    _full_game_splits: Title,
+    /// Include the Prologue
+    ///
+    /// When disabled, the run starts once the Prologue is over, as Mission 1 begins.
+    #[default = true]
+    include_prologue: bool,
+    /// Include the Tutorial
+    ///
+    /// Starts the run on the Tutorial as well, and splits once it's left. The New Game
+    /// that follows carries on with the same run rather than resetting it.
+    #[default = false]
+    include_tutorial: bool,
+    /// Split on every mission, following the layout's segments
+    ///
+    /// Overrides the per-mission toggles below. The splitter can't see the segments of
+    /// the layout, but the run ends on the last one, so a layout with N segments gets
+    /// exactly N splits on the first N missions completed.
+    #[default = false]
+    follow_segments: bool,
+    /// Split on Prologue completion
+    #[default = true]
+    split_prologue: bool,
+    /// Split on Mission 1 completion
+    #[default = true]
+    split_mission_1: bool,
+    /// Split on Mission 2 completion
+    #[default = true]
+    split_mission_2: bool,
+    /// Split on Mission 3 completion
+    #[default = true]
+    split_mission_3: bool,
+    /// Split on Mission 4 completion
+    #[default = true]
+    split_mission_4: bool,
+    /// Split on Mission 5 completion
+    #[default = true]
+    split_mission_5: bool,
+    /// Split on Mission 6 completion
+    #[default = true]
+    split_mission_6: bool,
+    /// Split on Mission 7 completion
+    #[default = true]
+    split_mission_7: bool,
+    /// Split on Mission 8 completion
+    #[default = true]
+    split_mission_8: bool,
+    /// Split on Mission 9 completion
+    #[default = true]
+    split_mission_9: bool,
+    /// Split on the final kill (Mission 10 completion)
+    #[default = true]
+    split_on_final_kill: bool,
+    /// Final split timing
+    ///
+    /// Bullet Cam Kill matches the usual route, where the final kill triggers the bullet
+    /// cam. Objective Complete is for runs where the final kill doesn't trigger it, while
+    /// Level End times the run up to the end of the ending sequence.
+    final_split_mode: FinalSplitMode,
+    /// Also split as the final cutscene of the finale begins
+    ///
+    /// Splits once the last objective before the final kill is complete, ahead of
+    /// the final split. Has no effect with the Objective Complete timing, which
+    /// already splits at that moment.
+    #[default = false]
+    split_finale_cutscene: bool,
+    /// Mission split timing
+    split_timing: SplitTiming,
+    /// Split on every objective completed within a mission
+    #[default = false]
+    objective_splits: bool,
+    /// Single Mission
+    _single_mission: Title,
+    /// Target mission
+    ///
+    /// Starts when the target mission is entered, and ends the run when it's
+    /// completed. Missions are told apart through their level codes, so only the
+    /// missions with a recorded code are offered. Ignored in IL mode.
+    single_mission: SingleMission,
+    /// IL Mode
+    _il_mode: Title,
     /// IL mode
     #[default = false]
     individual_level: bool,
+    /// IL start condition
+    il_start_mode: IlStartMode,
+    /// Bullet Cam Challenge
+    _bullet_cam: Title,
+    /// Split on every bullet cam kill, in any level, instead of the campaign splits
+    #[default = false]
+    split_on_bullet_cam: bool,
+    /// Bullet cam kills to split on
+    ///
+    /// No more splits fire once the target has been reached.
+    bullet_cam_target: BulletCamTarget,
+    /// Advanced
+    _advanced: Title,
+    /// Practice mode (never resets, whatever the other reset settings)
+    #[default = false]
+    practice_mode: bool,
+    /// Reset the run when the game stops answering
+    ///
+    /// Resets once every read has been failing long enough for the addresses to be
+    /// rescanned, or once nothing has been read at all for 10 seconds after attaching.
+    #[default = false]
+    reset_on_dead_reads: bool,
+    /// Debounce load removal
+    ///
+    /// Only pauses or resumes game time once the load state has held for about 50ms,
+    /// hiding flicker at the start and end of loads.
+    #[default = true]
+    debounce_loads: bool,
+    /// Minimum load duration
+    ///
+    /// Loads shorter than this aren't counted as loads, and their time gets credited back
+    /// to game time once they're over. Setting it always uses the computed timing method.
+    min_load_ms: MinLoad,
     /// Slow PC mode (reduces the refresh rate from 120hz to 60hz)
     #[default = false]
     slow_pc_mode: bool,
+    /// Adaptive refresh rate (drops to 30hz outside of loads)
+    #[default = false]
+    adaptive_tick_rate: bool,
+    /// Show the raw memory values as variables
+    #[default = false]
+    debug_variables: bool,
+    /// Missions Completed variable
+    ///
+    /// Whether the variable counts only the missions that split, following the
+    /// per-mission split toggles, or every mission completed in the run.
+    missions_counted: MissionsCounted,
+    /// Glitch filter (holds splits and mission boundaries back for a tick, to ignore values that are wrong for a single tick)
+    #[default = false]
+    glitch_filter: bool,
+    /// Log every start, split, reset and load removal decision
+    #[default = false]
+    verbose_logging: bool,
+    /// Offset table
+    ///
+    /// Forces one of the known offset tables, skipping version detection on any
+    /// build. Only read when attaching to the game.
+    offset_table: OffsetTable,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum StartMode {
+    /// New Game
+    #[default]
+    NewGame,
+    /// Load Game
+    LoadGame,
+    /// Either
+    Either,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum SplitTiming {
+    /// On Mission End
+    #[default]
+    OnMissionEnd,
+    /// On Mission Start
+    OnMissionStart,
+}
+
+/// How the finale gets considered complete for the final split.
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum FinalSplitMode {
+    /// Bullet Cam Kill
+    #[default]
+    BulletCamKill,
+    /// Objective Complete
+    ObjectiveComplete,
+    /// Level End
+    LevelEnd,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum StartOffset {
+    /// None
+    #[default]
+    None,
+    /// 100 ms
+    Ms100,
+    /// 250 ms
+    Ms250,
+    /// 500 ms
+    Ms500,
+    /// 1000 ms
+    Ms1000,
+}
+
+impl StartOffset {
+    fn seconds(self) -> f64 {
+        match self {
+            Self::None => 0.0,
+            Self::Ms100 => 0.1,
+            Self::Ms250 => 0.25,
+            Self::Ms500 => 0.5,
+            Self::Ms1000 => 1.0,
+        }
+    }
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum MinLoad {
+    /// Off
+    #[default]
+    Off,
+    /// 100 ms
+    Ms100,
+    /// 250 ms
+    Ms250,
+    /// 500 ms
+    Ms500,
+    /// 1000 ms
+    Ms1000,
+}
+
+impl MinLoad {
+    fn seconds(self) -> f64 {
+        match self {
+            Self::Off => 0.0,
+            Self::Ms100 => 0.1,
+            Self::Ms250 => 0.25,
+            Self::Ms500 => 0.5,
+            Self::Ms1000 => 1.0,
+        }
+    }
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum MenuBehavior {
+    /// Nothing
+    #[default]
+    Nothing,
+    /// Reset
+    Reset,
+    /// Pause
+    Pause,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum ResetGrace {
+    /// Off
+    Off,
+    /// 1 second
+    #[default]
+    OneSecond,
+    /// 3 seconds
+    ThreeSeconds,
+}
+
+impl ResetGrace {
+    fn seconds(self) -> f64 {
+        match self {
+            Self::Off => 0.0,
+            Self::OneSecond => 1.0,
+            Self::ThreeSeconds => 3.0,
+        }
+    }
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum TimingMethod {
+    /// Load removal
+    #[default]
+    LoadRemoval,
+    /// Computed
+    Computed,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum GameTimeUpdate {
+    /// Live
+    #[default]
+    Live,
+    /// On Split
+    OnSplit,
+    /// Final Only
+    FinalOnly,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum SingleMission {
+    /// Off
+    #[default]
+    Off,
+    /// Mission 10
+    Mission10,
+}
+
+impl SingleMission {
+    /// Position of the target mission in [`MISSIONS`].
+    fn index(self) -> Option<usize> {
+        match self {
+            Self::Off => None,
+            Self::Mission10 => Some(FINALE_INDEX),
+        }
+    }
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum BulletCamTarget {
+    /// No limit
+    #[default]
+    Unlimited,
+    /// 10 kills
+    Ten,
+    /// 25 kills
+    TwentyFive,
+    /// 50 kills
+    Fifty,
+    /// 100 kills
+    OneHundred,
+}
+
+impl BulletCamTarget {
+    fn count(self) -> Option<u32> {
+        match self {
+            Self::Unlimited => None,
+            Self::Ten => Some(10),
+            Self::TwentyFive => Some(25),
+            Self::Fifty => Some(50),
+            Self::OneHundred => Some(100),
+        }
+    }
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum MissionsCounted {
+    /// Split missions only
+    #[default]
+    Split,
+    /// All completed missions
+    All,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum OffsetTable {
+    /// Auto
+    #[default]
+    Auto,
+    /// Force OG
+    ForceOg,
+    /// Force Remastered
+    ForceRemastered,
+}
+
+#[derive(Gui, Clone, Copy, PartialEq, Eq)]
+enum IlStartMode {
+    /// Splash screen appears
+    #[default]
+    Splash,
+    /// Gameplay begins
+    Gameplay,
+}
+
+impl Settings {
+    /// Whether IL mode is in use, which it always is in `il-only` builds.
+    fn il_mode(&self) -> bool {
+        cfg!(feature = "il-only") || self.individual_level
+    }
+
+    /// Position of the single mission being run, if any, which IL mode takes precedence over.
+    fn single_mission(&self) -> Option<usize> {
+        match self.il_mode() {
+            true => None,
+            false => self.single_mission.index(),
+        }
+    }
+
+    /// The timing method in effect. Loads too short to count can only be given back to
+    /// game time when it's computed here, so a minimum load duration always computes it.
+    fn timing_method(&self) -> TimingMethod {
+        match self.min_load_ms {
+            MinLoad::Off => self.timing_method,
+            _ => TimingMethod::Computed,
+        }
+    }
+
+    /// Position in [`MISSIONS`] of the mission a full-game run starts with.
+    fn first_mission(&self) -> usize {
+        usize::from(!self.include_prologue)
+    }
+
+    fn split_on_mission(&self, index: usize) -> bool {
+        if self.follow_segments {
+            return true;
+        }
+
+        match index {
+            0 => self.split_prologue,
+            1 => self.split_mission_1,
+            2 => self.split_mission_2,
+            3 => self.split_mission_3,
+            4 => self.split_mission_4,
+            5 => self.split_mission_5,
+            6 => self.split_mission_6,
+            7 => self.split_mission_7,
+            8 => self.split_mission_8,
+            9 => self.split_mission_9,
+            10 => self.split_on_final_kill,
+            _ => true,
+        }
+    }
+}
+
+/// Offsets of the Remastered edition, in the order of the `Memory` fields.
+const REMASTERED_OFFSETS: [u64; 7] = [
+    0x799A77, 0x774FE3, 0x74C670, 0x7CFC7D, 0x76DD17, 0x7CF568, 0x799A63,
+];
+
+/// Offsets of the OG edition, in the order of the `Memory` fields.
+const OG_OFFSETS: [u64; 7] = [
+    0x689FE2, 0x67FC38, 0x653B40, 0x685F31, 0x65B917, 0x656F3C, 0x689FD2,
+];
+
+/// Ticks each step of resolving the addresses gets retried for before giving up,
+/// about 5 seconds at the default tick rate.
+const INIT_RETRY_TICKS: u32 = 600;
+
+/// Resolves the addresses, trying again for as long as it takes. The game gets reported
+/// as unsupported in the meantime, if the first attempt doesn't succeed, and the settings
+/// get read again before each attempt, so that another offset table can be picked.
+async fn resolve_addresses(
+    process: &Process,
+    process_name: &str,
+    settings: &mut Settings,
+) -> (Memory, GameVersion) {
+    loop {
+        settings.update();
+        if let Some(resolved) = Memory::init(process, process_name, settings.offset_table).await {
+            return resolved;
+        }
+        timer::set_variable("Status", "Unsupported / scanning…");
+        for _ in 0..INIT_RETRY_TICKS {
+            next_tick().await;
+        }
+    }
+}
+
+/// Tries `f` once per tick, like `retry`, but only for up to `ticks` ticks.
+async fn retry_for<T>(ticks: u32, mut f: impl FnMut() -> Option<T>) -> Option<T> {
+    for _ in 0..ticks {
+        if let Some(value) = f() {
+            return Some(value);
+        }
+        next_tick().await;
+    }
+    None
 }
 
+/// Addresses of the values read from the game.
+///
+/// All of these are static, version-relative addresses: each one is the main module base
+/// plus an offset that depends on the detected build, so they can be read directly.
 struct Memory {
+    main_module_size: u32,
+    /// Name of the offset table the addresses got resolved from
+    offset_table: &'static str,
     start: Address,
     load: Address,
     splash: Address,
     level: Address,
     bullet: Address,
     objective: Address,
+    /// Mission complete flag, set to 1 when the current mission gets completed and
+    /// cleared once the next level loads.
     mc: Address,
 }
 
 impl Memory {
-    async fn init(process: &Process, main_module_name: &str) -> Self {
-        let main_module_base = retry(|| process.get_module_address(main_module_name)).await;
-        let main_module_size = retry(|| pe::read_size_of_image(process, main_module_base)).await;
-
-        // let is_64_bit = retry(|| MachineType::pointer_size(MachineType::read(process, main_module_base)?)).await == PointerSize::Bit64;
-
-        match main_module_size {
-            0x1154000 => Self {
-                // remastered
-                start: main_module_base + 0x799A77,
-                load: main_module_base + 0x774FE3,
-                splash: main_module_base + 0x74C670,
-                level: main_module_base + 0x7CFC7D,
-                bullet: main_module_base + 0x76DD17,
-                objective: main_module_base + 0x7CF568,
-                mc: main_module_base + 0x799A63,
-            },
-            _ => Self {
-                // OG?
-                start: main_module_base + 0x689FE2,
-                load: main_module_base + 0x67FC38,
-                splash: main_module_base + 0x653B40,
-                level: main_module_base + 0x685F31,
-                bullet: main_module_base + 0x65B917,
-                objective: main_module_base + 0x656F3C,
-                mc: main_module_base + 0x689FD2,
-            },
+    async fn init(
+        process: &Process,
+        main_module_name: &str,
+        offset_table: OffsetTable,
+    ) -> Option<(Self, GameVersion)> {
+        // A launcher shim can leave the process and its main module under different
+        // names. The runtime can't enumerate the modules of a process, so the other
+        // known executable names get tried as the main module, in order.
+        let process_name = main_module_name;
+        let Some((main_module_name, main_module_base)) = retry_for(INIT_RETRY_TICKS, || {
+            core::iter::once(process_name)
+                .chain(PROCESS_NAMES.iter().chain(PORT_PROCESS_NAMES).copied())
+                .find_map(|name| Some((name, process.get_module_address(name).ok()?)))
+        })
+        .await
+        else {
+            asr::print_message("ERROR: the main module couldn't be found");
+            return None;
+        };
+        if main_module_name != process_name {
+            asr::print_limited::<128>(&format_args!(
+                "No {process_name} module, using {main_module_name} as the main module"
+            ));
+        }
+        // The image size can read as garbage while the game is still being loaded,
+        // which would otherwise leave it bound to the wrong offset table for good
+        const PLAUSIBLE_SIZES: core::ops::RangeInclusive<u32> = 0x100000..=0x4000000;
+        let mut main_module_size = 0;
+        for _ in 0..120 {
+            main_module_size =
+                pe::read_size_of_image(process, main_module_base).unwrap_or_default();
+            if PLAUSIBLE_SIZES.contains(&main_module_size) {
+                break;
+            }
+            next_tick().await;
+        }
+        if !PLAUSIBLE_SIZES.contains(&main_module_size) {
+            asr::print_limited::<64>(&format_args!(
+                "ERROR: implausible image size {main_module_size:#X}"
+            ));
+        }
+
+        let Some(pointer_size) = retry_for(INIT_RETRY_TICKS, || {
+            pe::MachineType::read(process, main_module_base)?.pointer_size()
+        })
+        .await
+        else {
+            asr::print_message("ERROR: the machine type of the main module couldn't be read");
+            return None;
+        };
+
+        let version = match offset_table {
+            OffsetTable::Auto => GameVersion::detect(
+                process,
+                main_module_name,
+                main_module_base,
+                main_module_size,
+            ),
+            OffsetTable::ForceOg => {
+                asr::print_message("Offset table forced: OG");
+                GameVersion::OgSteam
+            }
+            OffsetTable::ForceRemastered => {
+                asr::print_message("Offset table forced: Remastered");
+                GameVersion::RemasteredSteam
+            }
+        };
+
+        let (offset_table, offsets) = match (version, pointer_size) {
+            // Not verified against the Epic executable yet. The Steam Remastered
+            // offsets are the closest match until the actual ones are located.
+            (GameVersion::RemasteredEpic, _) => {
+                ("Remastered (Steam, unverified on Epic)", REMASTERED_OFFSETS)
+            }
+            (GameVersion::RemasteredSteam, _) => ("Remastered (Steam)", REMASTERED_OFFSETS),
+            // The OG executable also exists in 64-bit form, whose offsets haven't been
+            // mapped, and forcing the OG table on the 64-bit Remastered executable ends up
+            // here too. Nothing gets read from either, rather than from wrong addresses.
+            (GameVersion::OgSteam, PointerSize::Bit64) => {
+                asr::print_message("ERROR: 64-bit OG executable, no offset table for it");
+                return None;
+            }
+            (GameVersion::OgSteam, _) => ("OG", OG_OFFSETS),
+            // Builds that weren't recognized, such as a patched Remastered executable, fall
+            // back to the only mapped build with the same bitness, as the layouts of the
+            // 32-bit and 64-bit executables have nothing in common
+            (GameVersion::Other | GameVersion::Unknown, PointerSize::Bit64) => {
+                ("Remastered (fallback)", REMASTERED_OFFSETS)
+            }
+            (GameVersion::Other | GameVersion::Unknown, _) => ("OG (fallback)", OG_OFFSETS),
+        };
+        if matches!(version, GameVersion::Other | GameVersion::Unknown) {
+            asr::print_limited::<128>(&format_args!(
+                "WARNING: falling back to the {offset_table} offsets"
+            ));
+        }
+        let memory = Self::from_offsets(main_module_base, main_module_size, offsets, offset_table);
+
+        // Values in a game that has only just been launched may not have settled yet,
+        // so a few more checks are allowed before giving up on the addresses
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            match memory.validate(process) {
+                Ok(()) => break,
+                Err(name) if attempts >= 5 => {
+                    asr::print_limited::<128>(&format_args!(
+                        "ERROR: the {name} address holds an unexpected value, the game version is likely unsupported"
+                    ));
+                    break;
+                }
+                Err(_) => {
+                    for _ in 0..120 {
+                        next_tick().await;
+                    }
+                }
+            }
+        }
+
+        memory.log(main_module_base, main_module_size, version);
+
+        Some((memory, version))
+    }
+
+    /// Resolves the addresses from their offsets, given in the order of the fields.
+    fn from_offsets(
+        main_module_base: Address,
+        main_module_size: u32,
+        offsets: [u64; 7],
+        offset_table: &'static str,
+    ) -> Self {
+        let [start, load, splash, level, bullet, objective, mc] =
+            offsets.map(|offset| main_module_base + offset);
+        Self {
+            main_module_size,
+            offset_table,
+            start,
+            load,
+            splash,
+            level,
+            bullet,
+            objective,
+            mc,
+        }
+    }
+
+    /// Logs the resolved addresses relative to the module base, to make reports
+    /// about unsupported builds diagnosable from the log alone.
+    fn log(&self, main_module_base: Address, main_module_size: u32, version: GameVersion) {
+        let offset = |address: Address| address.value().wrapping_sub(main_module_base.value());
+
+        asr::print_limited::<256>(&format_args!(
+            "Address table: {version:?}, base {:#X}, size {main_module_size:#X}, start {:#X}, load {:#X}, splash {:#X}, level {:#X}, bullet {:#X}, objective {:#X}, mc {:#X}",
+            main_module_base.value(),
+            offset(self.start),
+            offset(self.load),
+            offset(self.splash),
+            offset(self.level),
+            offset(self.bullet),
+            offset(self.objective),
+            offset(self.mc),
+        ));
+    }
+
+    /// Checks that the resolved addresses hold plausible values, returning the
+    /// name of the first one that doesn't.
+    fn validate(&self, process: &Process) -> Result<(), &'static str> {
+        for (name, address) in [
+            ("start", self.start),
+            ("load", self.load),
+            ("splash", self.splash),
+            ("mc", self.mc),
+        ] {
+            if !matches!(process.read::<u8>(address), Ok(0 | 1)) {
+                return Err(name);
+            }
+        }
+
+        if !matches!(process.read::<u8>(self.objective), Ok(0..0x10)) {
+            return Err("objective");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum GameVersion {
+    OgSteam,
+    RemasteredSteam,
+    RemasteredEpic,
+    /// A build recognized by its executable, but whose offsets haven't been mapped
+    Other,
+    Unknown,
+}
+
+impl GameVersion {
+    fn detect(
+        process: &Process,
+        main_module_name: &str,
+        main_module_base: Address,
+        main_module_size: u32,
+    ) -> Self {
+        let timestamp = read_pe_timestamp(process, main_module_base).unwrap_or_default();
+
+        const REMASTERED_STEAM_SIZE: u32 = 0x1154000;
+
+        // The image size of the Epic build hasn't been recorded yet, so it gets
+        // told apart from unknown Steam builds by the missing Steamworks module
+        let is_steam = process.get_module_address("steam_api64.dll").is_ok();
+
+        // No build has had its timestamp recorded yet, so it's only logged, to tell builds
+        // of the same size apart once it is. The image size decides alongside the name.
+        let is_named = |name: &str| main_module_name.eq_ignore_ascii_case(name);
+        let version = match main_module_size {
+            REMASTERED_STEAM_SIZE => Self::RemasteredSteam,
+            _ if is_named(REMASTERED_PROCESS_NAME) && !is_steam => Self::RemasteredEpic,
+            _ if is_named(OG_PROCESS_NAME) => Self::OgSteam,
+            _ if is_named(REMASTERED_PROCESS_NAME) => Self::Other,
+            _ if PORT_PROCESS_NAMES.iter().any(|&name| is_named(name)) => Self::Other,
+            _ => Self::Unknown,
+        };
+
+        asr::print_limited::<128>(&format_args!(
+            "Detected game version: {version:?} (image size: {main_module_size:#X}, timestamp: {timestamp:#X})"
+        ));
+
+        match version {
+            Self::Other => {
+                asr::print_message("WARNING: recognized build without any mapped offsets")
+            }
+            Self::Unknown => asr::print_message("WARNING: unsupported game version"),
+            _ => (),
+        }
+
+        version
+    }
+
+    /// Reported through the "Status" variable once attached.
+    fn status(self) -> &'static str {
+        match self {
+            Self::OgSteam => "Attached (OG)",
+            Self::RemasteredSteam | Self::RemasteredEpic => "Attached (Remastered)",
+            Self::Other => "Attached (unmapped build)",
+            Self::Unknown => "Unsupported version",
         }
     }
 }
 
+/// Reads the `TimeDateStamp` field from the COFF header of the PE image at `module_base`.
+fn read_pe_timestamp(process: &Process, module_base: Address) -> Option<u32> {
+    let pe_header_offset = process.read::<u32>(module_base + 0x3C).ok()?;
+    process
+        .read(module_base + pe_header_offset as u64 + 0x8)
+        .ok()
+}
+
 #[derive(Default)]
 struct Watchers {
-    slow_pc_mode: bool,
+    tick_rate: f64,
+    game_time_paused: Option<bool>,
+    load_debounce: LoadDebounce,
+    /// When the previous tick ran
+    last_tick: Option<Instant>,
+    /// Consecutive ticks on which none of the core values could be read
+    failed_ticks: u32,
+    /// Split waiting for confirmation by the glitch filter, along with the
+    /// state of the game at the time it got triggered
+    pending_split: Option<(SplitKind, GameState)>,
+    /// Mission boundary waiting for confirmation by the glitch filter, as the state
+    /// of the game at the time it got seen
+    pending_mission_end: Option<GameState>,
+    /// Ticks gone by since a start that's being held back by the start offset
+    pending_start: Option<u32>,
+    /// Whether game time got paused by a start that couldn't tell if a load was going on
+    resume_pending: bool,
+    /// Ticks gone by without every value having been read at least once
+    unpaired_ticks: u32,
+    debug_variables: bool,
+    variables_initialized: bool,
     start_byte: Watcher<u8>,
     load_byte: Watcher<u8>,
     splash_byte: Watcher<u8>,
@@ -165,78 +1129,2227 @@ struct Watchers {
     bullet_cam: Watcher<u8>,
     objective: Watcher<u8>,
     mc: Watcher<u8>,
+    /// Number of successful start byte reads since attaching, up to the point it stops mattering
+    start_reads: u8,
+    /// Number of successful level reads since attaching, up to the point it stops mattering
+    level_reads: u8,
+    /// Whether the level currently loaded got loaded from the main menu
+    loaded_from_menu: bool,
+    /// Whether the Prologue of a New Game is being played
+    in_prologue: bool,
+    /// Whether the main menu has been seen at all since attaching
+    menu_seen: bool,
+    run: RunState,
+    /// Whether any of the per-mission in-game time variables got sent
+    mission_igt_published: bool,
+    /// Segment time last sent as a variable, in tenths of a second
+    published_segment: Option<u64>,
+    /// Image size of the game and name of the offset table the addresses got resolved from
+    build: Option<(u32, &'static str)>,
+    /// Whether the build has been sent as variables yet
+    build_sent: bool,
+    /// Phase last sent as a variable
+    phase: Option<Phase>,
+    /// Load count and load time last sent as variables
+    published_loads: Option<(u32, Duration)>,
+    /// Mission count last sent as a variable
+    published_missions: Option<usize>,
+    /// Mission the run was expected to be on when the level was last sent as a variable
+    published_expected: Option<usize>,
 }
 
-fn update_loop(process: &Process, memory: &Memory, watchers: &mut Watchers) {
-    watchers
-        .start_byte
-        .update_infallible(process.read(memory.start).unwrap_or_default());
-
-    watchers
-        .load_byte
-        .update_infallible(process.read(memory.load).unwrap_or_else(|_| 1));
-    watchers
-        .splash_byte
-        .update_infallible(process.read(memory.splash).unwrap_or_else(|_| 1));
-
-    watchers
-        .bullet_cam
-        .update_infallible(process.read(memory.bullet).unwrap_or_default());
-    watchers
-        .objective
-        .update_infallible(process.read(memory.objective).unwrap_or_default());
-    watchers
-        .mc
-        .update_infallible(process.read(memory.mc).unwrap_or_default());
+/// Holds back changes in the loading state until they have lasted for a while.
+#[derive(Clone, Copy, Default)]
+struct LoadDebounce {
+    /// Loading state reported after debouncing
+    state: Option<bool>,
+    /// Consecutive ticks the opposite state has been seen for
+    pending_ticks: u32,
+}
+
+impl LoadDebounce {
+    /// How long a change has to last for, in seconds. The number of ticks
+    /// follows the tick rate so the window stays the same in wall-clock time.
+    const WINDOW: f64 = 0.05;
+
+    fn update(&mut self, loading: Option<bool>, tick_rate: f64) -> Option<bool> {
+        let loading = loading?;
+
+        match self.state {
+            Some(state) if state != loading => {
+                self.pending_ticks += 1;
+                if self.pending_ticks as f64 >= tick_rate * Self::WINDOW {
+                    self.state = Some(loading);
+                    self.pending_ticks = 0;
+                }
+            }
+            _ => {
+                self.state = Some(loading);
+                self.pending_ticks = 0;
+            }
+        }
 
-    watchers
-        .level
-        .update_infallible(process.read(memory.level).unwrap_or_default());
+        self.state
+    }
 }
 
-fn start(watchers: &Watchers, settings: &Settings) -> bool {
-    match settings.individual_level {
-        true => watchers.splash_byte.pair.is_some_and(|val| {
-            val.changed_from_to(&0, &1)
-                && watchers
-                    .level
-                    .pair
-                    .is_some_and(|val| !val.current.matches("nu"))
-        }),
-        false => watchers
-            .start_byte
-            .pair
-            .is_some_and(|val| val.changed_to(&1)),
+/// Progress of the current run, cleared whenever the timer isn't running.
+#[derive(Clone, Copy, Default)]
+struct RunState {
+    /// Number of mission boundaries crossed so far
+    missions_completed: usize,
+    /// Number of mission boundaries that split so far
+    missions_split: usize,
+    /// Level code of the mission completed by the last split, so the same mission can't split twice
+    last_split_level: Option<ArrayCString<2>>,
+    /// Whether the final split of the run has already fired, after which
+    /// game time stays frozen and nothing else splits
+    final_split_done: bool,
+    /// Level code of the mission just completed, until the next one finishes loading
+    pending_boundary: Option<ArrayCString<2>>,
+    /// In-game time at the end of the last mission completed
+    igt_at_boundary: Duration,
+    /// Whether an IL run is still waiting for the opening load of its mission to end
+    opening_load: bool,
+    /// Real time since the start or the last split
+    segment_time: Duration,
+    /// Ticks gone by since the last split, if any split fired yet
+    ticks_since_split: Option<u32>,
+    /// Ticks gone by since the start
+    ticks_since_start: u32,
+    /// Number of loads gone through so far
+    load_count: u32,
+    /// Duration of the load going on, if any
+    current_load: Duration,
+    /// Part of the load going on that got removed from game time
+    current_load_removed: Duration,
+    /// Whether the load going on has lasted long enough to count as one
+    load_confirmed: bool,
+    /// Time spent inside loads so far
+    load_time: Duration,
+    /// Time spent outside of removed time so far while the timer was running
+    loadless_time: Duration,
+    /// Bullet cam kills split on so far, in the bullet cam challenge
+    bullet_cam_kills: u32,
+    /// Whether the tutorial has already split, in a run that includes it
+    tutorial_split: bool,
+    /// Whether the final cutscene of the finale has already split
+    finale_cutscene_split: bool,
+    /// Whether the tutorial has split and the New Game that follows it hasn't
+    /// been started yet, during which the run must not reset
+    awaiting_new_game: bool,
+}
+
+impl RunState {
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Records the mission completed by the split that just fired.
+    fn latch_split(&mut self, state: &GameState, kind: SplitKind) {
+        self.ticks_since_split = Some(0);
+        self.segment_time = Duration::ZERO;
+        self.final_split_done |= kind.is_final();
+
+        match kind {
+            SplitKind::LevelChange => {
+                self.last_split_level = Some(state.level.old);
+                self.missions_split += 1;
+            }
+            SplitKind::MissionStart => {
+                self.last_split_level = state.run.pending_boundary;
+                self.missions_split += 1;
+            }
+            SplitKind::FinalKill => self.last_split_level = Some(state.level.current),
+            SplitKind::BulletCam => self.bullet_cam_kills += 1,
+            SplitKind::FinaleCutscene => self.finale_cutscene_split = true,
+            SplitKind::Tutorial => {
+                self.tutorial_split = true;
+                self.awaiting_new_game = true;
+            }
+            SplitKind::MissionComplete | SplitKind::SingleMission | SplitKind::Objective => (),
+        }
+    }
+
+    /// Keeps count of the missions completed in the run, given the state the mission
+    /// boundary confirmed on this tick got seen with, if any. The mission completed
+    /// is forgotten once the next one has finished loading.
+    fn track_boundary(&mut self, state: &GameState, mission_end: Option<&GameState>) {
+        match mission_end {
+            Some(then) => {
+                self.missions_completed += 1;
+                self.pending_boundary = Some(then.level.old);
+            }
+            None if state.load_byte.changed_from_to(&1, &0) => self.pending_boundary = None,
+            None => (),
+        }
+    }
+
+    /// Moves the time and tick counters along by a tick that took `elapsed`, `counted`
+    /// towards the run unless the timer got paused by the runner.
+    fn count_tick(
+        &mut self,
+        state: &GameState,
+        settings: &Settings,
+        counted: bool,
+        elapsed: Duration,
+    ) {
+        let removed = time_removed(state, settings);
+        let flicker = self.track_loads(
+            state,
+            counted && removed,
+            settings.min_load_ms.seconds(),
+            elapsed,
+        );
+        if counted && !removed {
+            self.loadless_time += elapsed;
+        }
+
+        // A load too short to count got removed from the time counted here all the same,
+        // so its own duration is added back. The minimum load duration always has game
+        // time computed from this, as game time paused by LiveSplit can't be given back.
+        if let Some(flicker) = flicker
+            && !self.final_split_done
+        {
+            self.loadless_time += flicker;
+        }
+
+        self.segment_time += elapsed;
+        if let Some(ticks) = &mut self.ticks_since_split {
+            *ticks = ticks.saturating_add(1);
+        }
+        self.ticks_since_start = self.ticks_since_start.saturating_add(1);
+    }
+
+    /// Counts the loads, regardless of whether they are currently removed from game time.
+    ///
+    /// Pausing has to happen as soon as a load begins, while its duration is only known
+    /// once it's over, so a load only counts once it has lasted `min_load` seconds. A load
+    /// that ends short of it returns the time it took away from game time instead,
+    /// going by `removed`, for it to be credited back.
+    fn track_loads(
+        &mut self,
+        state: &GameState,
+        removed: bool,
+        min_load: f64,
+        tick: Duration,
+    ) -> Option<Duration> {
+        if loading(state) {
+            self.current_load += tick;
+            if removed {
+                self.current_load_removed += tick;
+            }
+            if self.load_confirmed {
+                self.load_time += tick;
+            } else if self.current_load.as_seconds_f64() >= min_load {
+                self.load_confirmed = true;
+                self.load_count += 1;
+                self.load_time += self.current_load;
+            }
+            return None;
+        }
+
+        let flicker = (!self.load_confirmed && self.current_load_removed > Duration::ZERO)
+            .then_some(self.current_load_removed);
+        self.current_load = Duration::ZERO;
+        self.current_load_removed = Duration::ZERO;
+        self.load_confirmed = false;
+        flicker
     }
 }
 
-fn is_loading(watchers: &Watchers, _settings: &Settings) -> Option<bool> {
-    Some(watchers.load_byte.pair?.current == 1 && watchers.splash_byte.pair?.current == 1)
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(test, derive(Debug))]
+enum SplitKind {
+    /// The level changed to a new mission, completing the previous one
+    #[cfg_attr(feature = "il-only", allow(dead_code))]
+    LevelChange,
+    /// The first load of a new mission cleared, completing the previous one
+    #[cfg_attr(feature = "il-only", allow(dead_code))]
+    MissionStart,
+    /// The ending sequence of the finale began
+    FinalKill,
+    /// The mission complete flag got set, in IL mode
+    MissionComplete,
+    /// The level changed away from the single mission being run
+    SingleMission,
+    /// An objective got completed within the current mission
+    Objective,
+    /// The bullet cam triggered, in the bullet cam challenge
+    BulletCam,
+    /// The tutorial got left, in a run that includes it
+    #[cfg_attr(feature = "il-only", allow(dead_code))]
+    Tutorial,
+    /// The final cutscene of the finale began, ahead of the final kill
+    FinaleCutscene,
 }
 
-fn split(watchers: &Watchers, settings: &Settings) -> bool {
-    match settings.individual_level {
-        true => watchers.mc.pair.is_some_and(|val| val.changed_to(&1)),
-        false => {
-            watchers.level.pair.is_some_and(|val| {
-                val.changed()
-                    && !val.current.is_empty()
-                    && !val.current.matches("nu")
-                    && !val.matches("Tu")
-            }) || (watchers
-                .level
-                .pair
-                .is_some_and(|val| val.current.matches("Br"))
-                && watchers.bullet_cam.pair.is_some_and(|val| val.current == 1)
-                && watchers.objective.pair.is_some_and(|val| val.current == 3))
+impl SplitKind {
+    /// Whether the split ends the run, with IL runs only ever splitting once.
+    fn is_final(self) -> bool {
+        match self {
+            Self::FinalKill | Self::MissionComplete | Self::SingleMission => true,
+            Self::LevelChange
+            | Self::MissionStart
+            | Self::Objective
+            | Self::BulletCam
+            | Self::Tutorial
+            | Self::FinaleCutscene => false,
         }
     }
 }
 
-fn game_time(_watchers: &Watchers, _settings: &Settings, _addresses: &Memory) -> Option<Duration> {
-    None
+/// Snapshot of the values read on the current tick, along with the progress of the run.
+///
+/// The splitting logic works exclusively off of this rather than the watchers,
+/// so it can be evaluated against any sequence of values without a live process.
+#[derive(Clone, Copy)]
+struct GameState {
+    start_byte: Pair<u8>,
+    load_byte: Pair<u8>,
+    splash_byte: Pair<u8>,
+    level: Pair<ArrayCString<2>>,
+    bullet_cam: Pair<u8>,
+    objective: Pair<u8>,
+    mc: Pair<u8>,
+    /// Whether the start byte has been read successfully twice, so
+    /// that its old value is always one that was actually read
+    start_settled: bool,
+    /// Whether the level code has been read successfully twice, so that
+    /// its first change can't just be the first read coming through
+    level_settled: bool,
+    /// Only full-game starts look at how the level got loaded
+    #[cfg_attr(feature = "il-only", allow(dead_code))]
+    loaded_from_menu: bool,
+    #[cfg_attr(feature = "il-only", allow(dead_code))]
+    in_prologue: bool,
+    menu_seen: bool,
+    run: RunState,
+    tick_rate: f64,
+}
+
+impl GameState {
+    /// Builds the snapshot, once every value has been read at least once.
+    ///
+    /// Watchers get rebuilt from scratch on every attach, the progress of the run aside,
+    /// and the first value read into a watcher is both its old and current value. This
+    /// means nothing that reacts to a change can fire on the first tick after attaching,
+    /// so re-attaching to a game that got relaunched mid-run never starts, splits or
+    /// resets on its own.
+    fn new(watchers: &Watchers) -> Option<Self> {
+        Some(Self {
+            start_byte: watchers.start_byte.pair?,
+            load_byte: watchers.load_byte.pair?,
+            splash_byte: watchers.splash_byte.pair?,
+            level: watchers.level.pair?,
+            bullet_cam: watchers.bullet_cam.pair?,
+            objective: watchers.objective.pair?,
+            mc: watchers.mc.pair?,
+            start_settled: watchers.start_reads >= 2,
+            level_settled: watchers.level_reads >= 2,
+            loaded_from_menu: watchers.loaded_from_menu,
+            in_prologue: watchers.in_prologue,
+            menu_seen: watchers.menu_seen,
+            run: watchers.run,
+            tick_rate: watchers.tick_rate,
+        })
+    }
+}
+
+/// What the game is currently doing, as far as the splitting logic is concerned.
+///
+/// A run goes from the `Menu` through `Loading`, `InMission` and `MissionComplete`
+/// for each mission, until it ends in the `Finale`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Menu,
+    Loading,
+    InMission,
+    MissionComplete,
+    Finale,
 }
 
-fn reset(_watchers: &Watchers, _settings: &Settings) -> bool {
-    false
+impl Phase {
+    fn of(state: &GameState) -> Self {
+        if in_menu(state) {
+            Self::Menu
+        } else if loading(state) {
+            Self::Loading
+        } else if state.mc.current == 1 {
+            Self::MissionComplete
+        } else if is_level(&state.level.current, FINALE) {
+            Self::Finale
+        } else {
+            Self::InMission
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Menu => "Menu",
+            Self::Loading => "Loading",
+            Self::InMission => "In Mission",
+            Self::MissionComplete => "Mission Complete",
+            Self::Finale => "Finale",
+        }
+    }
+}
+
+fn update_loop(process: &Process, memory: &Memory, watchers: &mut Watchers, settings: &Settings) {
+    // Every known build places the start and mc bytes within a few bytes of each other,
+    // so they're fetched with a single read. This takes the core values from 7 reads
+    // per tick down to 6, or from 840 down to 720 per second at 120hz. The others
+    // are too far apart from each other for a block read to pay off.
+    let (start, mc) = read_byte_pair(process, memory.start, memory.mc);
+
+    // Only the full-game finale split and the bullet cam challenge look at the
+    // bullet cam, so IL-only builds only read it for the latter
+    let bullet_cam = match cfg!(feature = "il-only") && !settings.split_on_bullet_cam {
+        true => Some(0),
+        false => process.read(memory.bullet).ok(),
+    };
+
+    update_watchers(
+        watchers,
+        Values {
+            start,
+            load: process.read(memory.load).ok(),
+            splash: process.read(memory.splash).ok(),
+            level: process.read(memory.level).ok(),
+            bullet_cam,
+            objective: process.read(memory.objective).ok(),
+            mc,
+        },
+    );
+
+    if let Some(level) = watchers.level.pair
+        && level.changed()
+        && !level.current.is_empty()
+        && level_code(&level.current).is_none()
+    {
+        asr::print_limited::<64>(&format_args!(
+            "Undecodable level code: {:?}",
+            level.current.as_bytes()
+        ));
+    }
+}
+
+/// Values read from the game on a single tick, each of them `None` if it failed to read.
+#[derive(Clone, Copy, Default)]
+struct Values {
+    start: Option<u8>,
+    load: Option<u8>,
+    splash: Option<u8>,
+    level: Option<ArrayCString<2>>,
+    bullet_cam: Option<u8>,
+    objective: Option<u8>,
+    mc: Option<u8>,
+}
+
+/// Feeds the values read on the current tick into the watchers.
+fn update_watchers(watchers: &mut Watchers, values: Values) {
+    // Every watcher keeps its last good value through a failed read. Feeding a default
+    // 0 instead would make the next successful read of 1 look like a new game being
+    // started, a load as a load ending, or a level as a level change.
+    update_or_keep(&mut watchers.start_byte, values.start);
+    if values.start.is_some() {
+        watchers.start_reads = watchers.start_reads.saturating_add(1);
+    }
+    update_or_keep(&mut watchers.load_byte, values.load);
+    update_or_keep(&mut watchers.splash_byte, values.splash);
+
+    match values.start.is_none() && values.load.is_none() && values.splash.is_none() {
+        true => watchers.failed_ticks += 1,
+        false => watchers.failed_ticks = 0,
+    }
+
+    update_or_keep(&mut watchers.bullet_cam, values.bullet_cam);
+    update_or_keep(&mut watchers.objective, values.objective);
+    update_or_keep(&mut watchers.mc, values.mc);
+
+    if values.level.is_some() {
+        watchers.level_reads = watchers.level_reads.saturating_add(1);
+    }
+    if let Some(level) = update_or_keep(&mut watchers.level, values.level) {
+        if level.changed() {
+            watchers.loaded_from_menu = is_level(&level.old, MENU);
+        }
+        watchers.menu_seen |= is_level(&level.current, MENU);
+    }
+}
+
+/// Updates a watcher with a value that may have failed to read.
+///
+/// A failed read feeds the last good value back in, so the pair reads as unchanged
+/// rather than repeating the change of the previous tick. Until a first read goes
+/// through, the watcher stays empty.
+fn update_or_keep<T: Copy>(watcher: &mut Watcher<T>, value: Option<T>) -> Option<&mut Pair<T>> {
+    let value = value.or_else(|| watcher.pair.map(|val| val.current))?;
+    Some(watcher.update_infallible(value))
+}
+
+/// Reads two bytes with a single block read if they're close enough to each
+/// other, or with individual reads otherwise.
+fn read_byte_pair(process: &Process, a: Address, b: Address) -> (Option<u8>, Option<u8>) {
+    const BLOCK_SIZE: usize = 0x40;
+
+    let low = a.value().min(b.value());
+    let high = a.value().max(b.value());
+
+    match high - low < BLOCK_SIZE as u64 {
+        true => {
+            // Only the bytes from one address to the other get read, as the ones
+            // past the last of them may not be mapped
+            let mut block = [0; BLOCK_SIZE];
+            let block = &mut block[..=(high - low) as usize];
+            match process.read_into_buf(Address::new(low), block) {
+                Ok(()) => (
+                    Some(block[(a.value() - low) as usize]),
+                    Some(block[(b.value() - low) as usize]),
+                ),
+                Err(_) => (None, None),
+            }
+        }
+        false => (process.read(a).ok(), process.read(b).ok()),
+    }
+}
+
+/// Real time gone by since the previous tick, which the runtime doesn't guarantee to be
+/// a steady `1 / tick_rate`. Nothing has gone by on the first tick after attaching.
+fn tick_duration(watchers: &mut Watchers) -> Duration {
+    let now = Instant::now();
+    let elapsed = watchers.last_tick.map_or(Duration::ZERO, |last| {
+        Duration::seconds_f64(now.duration_since(last).as_secs_f64())
+    });
+    watchers.last_tick = Some(now);
+    elapsed
+}
+
+fn tick_rate(watchers: &Watchers, settings: &Settings) -> f64 {
+    let max = match settings.slow_pc_mode {
+        true => 60.0,
+        false => 120.0,
+    };
+
+    // Precision only matters for catching the exact start and end of a load,
+    // and both bytes stay at 0 throughout steady gameplay
+    let near_load = [&watchers.load_byte, &watchers.splash_byte]
+        .iter()
+        .any(|watcher| {
+            watcher
+                .pair
+                .is_none_or(|val| val.old == 1 || val.current == 1)
+        });
+
+    match settings.adaptive_tick_rate && !near_load {
+        true => 30.0,
+        false => max,
+    }
+}
+
+fn update_variables(watchers: &mut Watchers, settings: &Settings) {
+    // Variables are only sent when their value changes, unless they
+    // haven't been sent at all yet since attaching or enabling them
+    let refresh =
+        !watchers.variables_initialized || watchers.debug_variables != settings.debug_variables;
+    watchers.variables_initialized = true;
+    watchers.debug_variables = settings.debug_variables;
+
+    // Missions without a recorded code are named after the mission the run is expected
+    // to be on, which only gets counted once the level change has been seen
+    let expected = expected_mission(watchers, settings);
+    if let Some(level) = watchers.level.pair
+        && (refresh || level.changed() || watchers.published_expected != expected)
+    {
+        watchers.published_expected = expected;
+        let mut buf = [0; 12];
+        timer::set_variable("Level", level_name(&level.current, expected, &mut buf));
+    }
+
+    if let Some(state) = GameState::new(watchers) {
+        let phase = Phase::of(&state);
+        if refresh || watchers.phase != Some(phase) {
+            watchers.phase = Some(phase);
+            timer::set_variable("Phase", phase.name());
+        }
+    }
+
+    // Only sent every tenth of a second, which is as precise as it needs to be at a glance
+    let segment = (watchers.run.segment_time.as_seconds_f64() * 10.0) as u64;
+    if refresh || watchers.published_segment != Some(segment) {
+        watchers.published_segment = Some(segment);
+        timer::set_variable_float("Segment Time", segment as f64 / 10.0);
+    }
+
+    let loads = (watchers.run.load_count, watchers.run.load_time);
+    if refresh || watchers.published_loads != Some(loads) {
+        watchers.published_loads = Some(loads);
+        timer::set_variable_int("Load Count", loads.0);
+        // Rounded down to the millisecond, to keep the value readable
+        let millis = (loads.1.as_seconds_f64() * 1000.0) as u64;
+        timer::set_variable_float("Time Lost to Loads", millis as f64 / 1000.0);
+    }
+
+    let missions = match settings.missions_counted {
+        MissionsCounted::Split => watchers.run.missions_split,
+        MissionsCounted::All => watchers.run.missions_completed,
+    };
+    if refresh || watchers.published_missions != Some(missions) {
+        watchers.published_missions = Some(missions);
+        timer::set_variable_int("Missions Completed", missions);
+    }
+
+    if settings.debug_variables
+        && let Some((size, offset_table)) = watchers.build
+        && (refresh || !watchers.build_sent)
+    {
+        watchers.build_sent = true;
+        let hex = hex_u32(size);
+        timer::set_variable(
+            "Module Size",
+            core::str::from_utf8(&hex).unwrap_or_default(),
+        );
+        timer::set_variable("Offset Table", offset_table);
+    }
+
+    if settings.debug_variables {
+        for (key, watcher) in [
+            ("Start", &watchers.start_byte),
+            ("Load", &watchers.load_byte),
+            ("Splash", &watchers.splash_byte),
+        ] {
+            if let Some(val) = watcher.pair
+                && (refresh || val.changed())
+            {
+                timer::set_variable_int(key, val.current);
+            }
+        }
+
+        if let (Some(objective), Some(level)) = (watchers.objective.pair, watchers.level.pair)
+            && (refresh || objective.changed() || level.changed())
+        {
+            timer::set_variable(
+                "Objective",
+                objective_name(&level.current, objective.current),
+            );
+        }
+    }
+}
+
+/// Variables holding the in-game time of each mission, indexed like [`MISSIONS`].
+const MISSION_IGT_KEYS: [&str; MISSIONS.len()] = [
+    "Prologue IGT",
+    "Mission 1 IGT",
+    "Mission 2 IGT",
+    "Mission 3 IGT",
+    "Mission 4 IGT",
+    "Mission 5 IGT",
+    "Mission 6 IGT",
+    "Mission 7 IGT",
+    "Mission 8 IGT",
+    "Mission 9 IGT",
+    "Mission 10 IGT",
+];
+
+/// Publishes the in-game time of the mission just completed, given the total for the run.
+fn record_mission_igt(watchers: &mut Watchers, index: usize, total: Duration) {
+    if let Some(key) = MISSION_IGT_KEYS.get(index) {
+        let mission = total - watchers.run.igt_at_boundary;
+        // Rounded down to the millisecond, to keep the value readable
+        let millis = (mission.as_seconds_f64() * 1000.0) as u64;
+        timer::set_variable_float(key, millis as f64 / 1000.0);
+        watchers.mission_igt_published = true;
+    }
+    watchers.run.igt_at_boundary = total;
+}
+
+fn clear_mission_igt(watchers: &mut Watchers) {
+    if watchers.mission_igt_published {
+        for key in MISSION_IGT_KEYS {
+            timer::set_variable(key, "");
+        }
+        watchers.mission_igt_published = false;
+    }
+}
+
+/// Formats a value as `0x` followed by 8 uppercase hexadecimal digits.
+fn hex_u32(value: u32) -> [u8; 10] {
+    let mut hex = *b"0x00000000";
+    for (i, digit) in hex[2..].iter_mut().rev().enumerate() {
+        *digit = b"0123456789ABCDEF"[(value >> (i * 4)) as usize & 0xF];
+    }
+    hex
+}
+
+/// Resets a run that can't go on because the game stopped answering.
+fn safety_reset(timer: &mut impl Timer, watchers: &mut Watchers, reason: &str) {
+    if [TimerState::Running, TimerState::Paused].contains(&timer.state()) {
+        asr::print_limited::<128>(&format_args!("Resetting the run, as {reason}"));
+        timer.reset();
+        clear_run(watchers);
+    }
+}
+
+/// Forgets the progress of the run, once the timer got reset.
+fn clear_run(watchers: &mut Watchers) {
+    watchers.run.reset();
+    watchers.pending_split = None;
+    watchers.pending_mission_end = None;
+    clear_mission_igt(watchers);
+}
+
+fn log_decision(event: &str, state: &GameState, settings: &Settings) {
+    if settings.verbose_logging {
+        asr::print_limited::<256>(&format_args!(
+            "{event}: level {} -> {}, start {}, load {}, splash {}, bullet cam {}, objective {}, mc {}",
+            level_code(&state.level.old).unwrap_or_default(),
+            level_code(&state.level.current).unwrap_or_default(),
+            state.start_byte.current,
+            state.load_byte.current,
+            state.splash_byte.current,
+            state.bullet_cam.current,
+            state.objective.current,
+            state.mc.current,
+        ));
+    }
+}
+
+/// Starts the timer, along with everything that needs to be set up for a new run.
+fn start_run(
+    timer: &mut impl Timer,
+    watchers: &mut Watchers,
+    state: &GameState,
+    settings: &Settings,
+) {
+    timer.start();
+    pause_game_time(timer, watchers, settings);
+    watchers.run.opening_load = settings.il_mode();
+
+    // The state at the start is taken as is, rather than debounced
+    let loading = is_loading(state, settings);
+    watchers.load_debounce = LoadDebounce::default();
+    watchers.load_debounce.update(loading, watchers.tick_rate);
+    match loading {
+        Some(true) => pause_game_time(timer, watchers, settings),
+        Some(false) => resume_game_time(timer, watchers, settings),
+        None => watchers.resume_pending = true,
+    }
+}
+
+/// Pauses game time, unless load removal is disabled, in which case game time
+/// never gets paused at all and keeps following real time.
+fn pause_game_time(timer: &mut impl Timer, watchers: &mut Watchers, settings: &Settings) {
+    if !settings.load_removal {
+        return;
+    }
+    timer.pause_game_time();
+
+    if settings.verbose_logging && watchers.game_time_paused != Some(true) {
+        asr::print_message("Game time paused");
+    }
+    watchers.game_time_paused = Some(true);
+}
+
+fn resume_game_time(timer: &mut impl Timer, watchers: &mut Watchers, settings: &Settings) {
+    timer.resume_game_time();
+
+    if settings.verbose_logging && watchers.game_time_paused != Some(false) {
+        asr::print_message("Game time resumed");
+    }
+    watchers.game_time_paused = Some(false);
+}
+
+fn start(state: &GameState, settings: &Settings) -> bool {
+    // Attaching while the game is already in a mission can't produce a valid
+    // start, so nothing starts until the main menu has been passed through
+    if !state.menu_seen {
+        return false;
+    }
+
+    if let Some(target) = settings.single_mission() {
+        return mission_index(&state.level.current) == Some(target)
+            && state.load_byte.changed_from_to(&1, &0);
+    }
+
+    #[cfg(not(feature = "il-only"))]
+    if !settings.il_mode() {
+        return full_game_start(state, settings);
+    }
+
+    // The Remastered publisher logos toggle the splash byte too, but they play before
+    // the main menu shows up and while the level code is still a placeholder, so both
+    // the check above and the real mission check filter them out. The latter also keeps
+    // the tutorial from starting, while the Prologue starts like any other mission.
+    is_real_mission(&state.level.current)
+        && match settings.il_start_mode {
+            IlStartMode::Splash => state.splash_byte.changed_from_to(&0, &1),
+            // The mission's opening load clearing is the moment control goes to the player
+            IlStartMode::Gameplay => state.load_byte.changed_from_to(&1, &0),
+        }
+}
+
+/// Whether a full-game run starts on the current tick.
+#[cfg(not(feature = "il-only"))]
+fn full_game_start(state: &GameState, settings: &Settings) -> bool {
+    let new_game = match settings.include_prologue {
+        true => new_game_started(state),
+        false => state.in_prologue && mission_ended(state),
+    } || settings.include_tutorial && tutorial_started(state);
+
+    // Continuing a saved campaign doesn't go through the start byte at all, so it's
+    // detected as the end of a load that began on the main menu instead
+    let load_game = state.loaded_from_menu
+        && is_real_mission(&state.level.current)
+        && state.load_byte.changed_from_to(&1, &0);
+
+    match settings.start_mode {
+        StartMode::NewGame => new_game,
+        StartMode::LoadGame => load_game,
+        StartMode::Either => new_game || load_game,
+    }
+}
+
+/// Whether the tutorial just got loaded.
+///
+/// Selecting it goes through the start byte just like a New Game does, but the main
+/// menu is still loaded at that moment, so it's the level change that tells it apart.
+fn tutorial_started(state: &GameState) -> bool {
+    state.level_settled && state.level.changed() && is_level(&state.level.current, TUTORIAL)
+}
+
+/// Whether a New Game just got selected.
+///
+/// The main menu is still loaded at that moment, so selecting the tutorial looks just
+/// the same. A run it started gets reset once the tutorial loads, unless it's included.
+fn new_game_started(state: &GameState) -> bool {
+    state.start_settled && state.start_byte.changed_from_to(&0, &1)
+}
+
+fn is_loading(state: &GameState, settings: &Settings) -> Option<bool> {
+    // Game time got frozen by the final split, and the credits and the
+    // return to the menu that follow must not resume it
+    if state.run.final_split_done {
+        return None;
+    }
+
+    if !settings.load_removal {
+        return Some(false);
+    }
+
+    // Game time is entirely driven by game_time() when the time outside of loads
+    // is being computed here
+    if settings.timing_method() == TimingMethod::Computed {
+        return Some(true);
+    }
+
+    Some(time_removed(state, settings))
+}
+
+/// Whether the current tick doesn't count towards game time, because of a load
+/// or the main menu.
+fn time_removed(state: &GameState, settings: &Settings) -> bool {
+    if in_menu(state) {
+        match settings.menu_behavior {
+            MenuBehavior::Pause => return true,
+            // The bytes report the main menu as loading too, which isn't always wanted
+            _ if !settings.remove_menu_time => return false,
+            _ => (),
+        }
+    }
+
+    loading(state)
+}
+
+/// Whether the load and splash bytes report a load. Both hold 1 for the whole
+/// duration of a load, including the main menu, and 0 otherwise.
+fn loading(state: &GameState) -> bool {
+    state.load_byte.current == 1 && state.splash_byte.current == 1
+}
+
+/// Whether the player is sitting in the main menu rather than in, or in between, missions.
+///
+/// Only the level code tells. The start byte is only ever set by selecting a New Game,
+/// and stays 0 for the whole of a campaign continued from a save.
+fn in_menu(state: &GameState) -> bool {
+    is_level(&state.level.current, MENU)
+}
+
+/// Whether the level just changed back to the main menu, which is what quitting
+/// out of a campaign looks like, however it got started.
+fn returned_to_menu(state: &GameState) -> bool {
+    state.level_settled && state.level.changed() && in_menu(state)
+}
+
+/// Decides whether the current tick splits, and as what.
+///
+/// Once the final split of the run has fired, nothing splits anymore until the run
+/// gets reset, so a layout with more segments than the run produces doesn't get them
+/// filled by the credits, the return to the menu or anything loaded after that. Every
+/// split goes through here, the glitch filter included.
+fn split(state: &GameState, settings: &Settings) -> Option<SplitKind> {
+    if state.run.final_split_done {
+        return None;
+    }
+
+    // The bullet cam challenge counts kills anywhere, level progression aside, and
+    // replaces every other split while it's enabled
+    if settings.split_on_bullet_cam {
+        let target_reached = settings
+            .bullet_cam_target
+            .count()
+            .is_some_and(|target| state.run.bullet_cam_kills >= target);
+        return (!target_reached && state.bullet_cam.changed_from_to(&0, &1))
+            .then_some(SplitKind::BulletCam);
+    }
+
+    // The final kill can only come once the cutscene has split, should both be read
+    // on the same tick, and the objective that starts the cutscene splits as such
+    if finale_cutscene_started(state, settings) {
+        return Some(SplitKind::FinaleCutscene);
+    }
+
+    // The objective counts up as objectives get completed within a mission (the finale
+    // reaches 3 right before the final kill) and drops back down when a level loads,
+    // which must not split
+    if settings.objective_splits && !state.level.changed() && state.objective.increased() {
+        return Some(SplitKind::Objective);
+    }
+
+    if let Some(target) = settings.single_mission() {
+        return match target == FINALE_INDEX {
+            true => final_kill(state, settings.final_split_mode).then_some(SplitKind::FinalKill),
+            false => (mission_ended(state) && mission_index(&state.level.old) == Some(target))
+                .then_some(SplitKind::SingleMission),
+        };
+    }
+
+    #[cfg(not(feature = "il-only"))]
+    if !settings.il_mode() {
+        return full_game_split(state, settings);
+    }
+
+    state
+        .mc
+        .changed_to(&1)
+        .then_some(SplitKind::MissionComplete)
+}
+
+/// Decides whether the current tick splits a full-game run, and as what.
+#[cfg(not(feature = "il-only"))]
+fn full_game_split(state: &GameState, settings: &Settings) -> Option<SplitKind> {
+    // A level-change split completes the previous level, while the finale
+    // split completes the current one. Either way, a mission that already
+    // split can't split again.
+    let latched = |code: &ArrayCString<2>| state.run.last_split_level == Some(*code);
+    let finale = match settings.final_split_mode {
+        FinalSplitMode::LevelEnd => &state.level.old,
+        _ => &state.level.current,
+    };
+
+    // When splitting on the next mission's start, the boundary has already been
+    // counted by the time its load clears
+    let boundary = match settings.split_timing {
+        SplitTiming::OnMissionEnd => mission_ended(state).then(|| {
+            let count = state.run.missions_completed + settings.first_mission();
+            (state.level.old, completed_mission(state, count))
+        }),
+        SplitTiming::OnMissionStart => state
+            .run
+            .pending_boundary
+            .filter(|_| state.load_byte.changed_from_to(&1, &0))
+            .map(|level| {
+                let count =
+                    state.run.missions_completed.saturating_sub(1) + settings.first_mission();
+                (level, mission_index(&level).unwrap_or(count))
+            }),
+    };
+
+    if settings.include_tutorial
+        && !state.run.tutorial_split
+        && state.level_settled
+        && state.level.changed()
+        && is_level(&state.level.old, TUTORIAL)
+    {
+        Some(SplitKind::Tutorial)
+    } else if let Some((completed, index)) = boundary
+        && !latched(&completed)
+        && settings.split_on_mission(index)
+    {
+        Some(match settings.split_timing {
+            SplitTiming::OnMissionEnd => SplitKind::LevelChange,
+            SplitTiming::OnMissionStart => SplitKind::MissionStart,
+        })
+    } else if settings.split_on_mission(FINALE_INDEX)
+        && !latched(finale)
+        && final_kill(state, settings.final_split_mode)
+    {
+        Some(SplitKind::FinalKill)
+    } else {
+        None
+    }
+}
+
+/// Runs the split action, holding each split back for a tick when the glitch filter is on.
+///
+/// A held back split only goes through if the level, the objective and the mission
+/// complete flag still read the same on the next tick, so a value that's wrong for
+/// one tick only can't split. The split is then carried out with the state it got
+/// triggered with.
+fn confirm_split(
+    watchers: &mut Watchers,
+    state: &GameState,
+    settings: &Settings,
+) -> Option<(SplitKind, GameState)> {
+    if !settings.glitch_filter {
+        watchers.pending_split = None;
+        return split(state, settings).map(|kind| (kind, *state));
+    }
+
+    match watchers.pending_split.take() {
+        Some((kind, then)) => (then.level.current == state.level.current
+            && then.objective.current == state.objective.current
+            && then.mc.current == state.mc.current)
+            .then_some((kind, then)),
+        None => {
+            watchers.pending_split = split(state, settings).map(|kind| (kind, *state));
+            None
+        }
+    }
+}
+
+/// Returns the state a mission boundary got seen with, holding it back for a tick
+/// when the glitch filter is on, just like the splits.
+///
+/// A level that reads wrong for one tick only would otherwise count as two
+/// boundaries, one on its way in and one on its way out.
+fn confirm_mission_end(
+    watchers: &mut Watchers,
+    state: &GameState,
+    settings: &Settings,
+) -> Option<GameState> {
+    if !settings.glitch_filter {
+        watchers.pending_mission_end = None;
+        return mission_ended(state).then_some(*state);
+    }
+
+    match watchers.pending_mission_end.take() {
+        Some(then) => (then.level.current == state.level.current).then_some(then),
+        None => {
+            watchers.pending_mission_end = mission_ended(state).then_some(*state);
+            None
+        }
+    }
+}
+
+/// Records the in-game time of the mission just left, given the state the mission
+/// boundary got seen with.
+fn record_completed_mission_igt(
+    watchers: &mut Watchers,
+    then: &GameState,
+    game_time: Option<Duration>,
+    settings: &Settings,
+) {
+    if let Some(x) = game_time
+        && !settings.il_mode()
+        && settings.single_mission().is_none()
+    {
+        let index = completed_mission(
+            then,
+            watchers.run.missions_completed + settings.first_mission(),
+        );
+        record_mission_igt(watchers, index, x);
+    }
+}
+
+/// Keeps track of whether the Prologue of a New Game is being played.
+///
+/// A New Game always opens with the Prologue, which lasts until the first mission
+/// boundary, unless the campaign gets quit before that. Selecting the tutorial looks
+/// just like a New Game until it's loaded.
+fn track_prologue(watchers: &mut Watchers, state: &GameState, mission_ended: bool) {
+    if new_game_started(state) {
+        watchers.in_prologue = true;
+        watchers.run.awaiting_new_game = false;
+    } else if mission_ended || tutorial_started(state) || returned_to_menu(state) {
+        watchers.in_prologue = false;
+    }
+}
+
+/// Whether the Brandenburg Gate finale has been completed, according to the chosen mode.
+fn final_kill(state: &GameState, mode: FinalSplitMode) -> bool {
+    // The bullet cam triggers on long shots in every mission and objectives are counted
+    // everywhere, so none of this means anything outside of the finale
+    let finale = match mode {
+        FinalSplitMode::LevelEnd => &state.level.old,
+        _ => &state.level.current,
+    };
+    if !is_level(finale, FINALE) {
+        return false;
+    }
+
+    match mode {
+        FinalSplitMode::BulletCamKill => {
+            state.bullet_cam.current == 1 && state.objective.current == FINALE_OBJECTIVES
+        }
+        FinalSplitMode::ObjectiveComplete => state.objective.changed_to(&FINALE_OBJECTIVES),
+        FinalSplitMode::LevelEnd => state.level.changed(),
+    }
+}
+
+/// Whether the final cutscene of the finale just began, when it's meant to split
+/// on its own. It only ever splits once per run.
+fn finale_cutscene_started(state: &GameState, settings: &Settings) -> bool {
+    // Only runs that split on the final kill get the finale split in two
+    let finale_splits = match settings.single_mission() {
+        Some(target) => target == FINALE_INDEX,
+        None => !settings.il_mode() && settings.split_on_mission(FINALE_INDEX),
+    };
+
+    finale_splits
+        && settings.split_finale_cutscene
+        && settings.final_split_mode != FinalSplitMode::ObjectiveComplete
+        && !state.run.finale_cutscene_split
+        && is_level(&state.level.current, FINALE)
+        && state.objective.changed_to(&FINALE_OBJECTIVES)
+}
+
+/// Objectives completed in the finale by the time only the final kill is left.
+const FINALE_OBJECTIVES: u8 = 3;
+
+/// Decodes the objective counter, for the "Objective" variable.
+///
+/// The counter holds the number of objectives completed in the current mission, and
+/// goes back to 0 whenever a level loads. In the Brandenburg Gate finale it reaches 3
+/// once the final kill is the only thing left, which is what the finale split goes by.
+/// The objectives of the other missions haven't been mapped, so they're only counted.
+fn objective_name(level: &ArrayCString<2>, objective: u8) -> &'static str {
+    match (is_level(level, FINALE), objective) {
+        (true, FINALE_OBJECTIVES) => "Final kill",
+        (_, 0) => "None completed",
+        (_, 1) => "1 completed",
+        (_, 2) => "2 completed",
+        (_, 3) => "3 completed",
+        _ => "More than 3 completed",
+    }
+}
+
+/// Whether the level just changed to a new mission, marking the end of the previous one.
+///
+/// Each change between two values counts as exactly one boundary, so a mission
+/// that's never observed can't split. That doesn't happen on a real transition,
+/// even at the 60hz of the slow PC mode: the level only ever changes while a load
+/// is going on, and every load lasts far longer than a tick, so each mission played
+/// gets observed for many ticks.
+///
+/// Loading a mission from the main menu completes nothing, whether it's the Prologue
+/// of a New Game, a saved campaign, a pick out of the mission select or the way back
+/// from a paused run, so it's never a boundary.
+fn mission_ended(state: &GameState) -> bool {
+    state.level_settled
+        && state.level.changed()
+        && !is_level(&state.level.old, MENU)
+        && is_real_mission(&state.level.current)
+}
+
+/// Position in [`MISSIONS`] of the mission that just ended, from its level code,
+/// or else `count`, the number of missions completed in the run.
+fn completed_mission(state: &GameState, count: usize) -> usize {
+    mission_index(&state.level.old).unwrap_or(count)
+}
+
+/// Level code reported while on the main menu.
+const MENU: &str = "nu";
+/// Level code of the tutorial, which is never part of a run.
+const TUTORIAL: &str = "Tu";
+/// Level code of the Brandenburg Gate finale.
+const FINALE: &str = "Br";
+
+/// Decodes a level code, which is made of up to two ASCII letters or digits.
+///
+/// The buffer gets cut at the first null byte, so a one-letter code decodes to just
+/// that letter whatever follows it, and only ever matches a one-letter code. Anything
+/// else, such as garbage read while the level is being swapped out, decodes to `None`
+/// and never matches any code.
+fn level_code(level: &ArrayCString<2>) -> Option<&str> {
+    level
+        .validate_utf8()
+        .ok()
+        .filter(|code| code.bytes().all(|byte| byte.is_ascii_alphanumeric()))
+}
+
+/// Whether a level code decodes to the given one.
+fn is_level(level: &ArrayCString<2>, code: &str) -> bool {
+    level_code(level) == Some(code)
+}
+
+/// Whether a level code belongs to an actual mission, rather than
+/// the tutorial, the main menu or no level at all.
+fn is_real_mission(level: &ArrayCString<2>) -> bool {
+    level_code(level).is_some_and(|code| !code.is_empty() && code != MENU && code != TUTORIAL)
+}
+
+/// A mission as it appears in the [`MISSIONS`] table.
+struct Mission {
+    /// Level code, or `None` if it hasn't been recorded yet
+    code: Option<&'static str>,
+    /// Name shown in the "Level" variable
+    name: &'static str,
+}
+
+impl Mission {
+    const fn new(code: Option<&'static str>, name: &'static str) -> Self {
+        Self { code, name }
+    }
+}
+
+/// The campaign missions in the order they're played. This is the one place that
+/// ties level codes to missions: the split toggles, the per-mission variables
+/// and the "Level" variable all go by it.
+///
+/// The DLC missions bundled with the Remastered edition get added once their
+/// level codes are recorded, as they can't be told apart from a campaign mission
+/// without them.
+///
+/// Only the finale is named after its location so far, the other campaign
+/// missions going by their number until their names are checked against the game.
+const MISSIONS: [Mission; 11] = [
+    // Until its code is recorded, the Prologue is told apart as the
+    // part of a New Game that comes before the first mission boundary.
+    Mission::new(None, "Prologue"),
+    Mission::new(None, "Mission 1"),
+    Mission::new(None, "Mission 2"),
+    Mission::new(None, "Mission 3"),
+    Mission::new(None, "Mission 4"),
+    Mission::new(None, "Mission 5"),
+    Mission::new(None, "Mission 6"),
+    Mission::new(None, "Mission 7"),
+    Mission::new(None, "Mission 8"),
+    Mission::new(None, "Mission 9"),
+    Mission::new(Some(FINALE), "Mission 10: Brandenburg Gate"),
+];
+
+/// Position of the Brandenburg Gate finale in [`MISSIONS`].
+const FINALE_INDEX: usize = 10;
+
+/// Maps a level code to its position in [`MISSIONS`], with 0 being the Prologue.
+///
+/// Codes that aren't recorded return `None`, in which case the number
+/// of missions completed in the current run is used instead.
+fn mission_index(level: &ArrayCString<2>) -> Option<usize> {
+    MISSIONS
+        .iter()
+        .position(|mission| mission.code.is_some_and(|code| is_level(level, code)))
+}
+
+/// Position in [`MISSIONS`] of the mission a full-game run is expected to be on,
+/// going by the number of missions completed so far, or the Prologue of a New Game
+/// that hasn't started a run.
+fn expected_mission(watchers: &Watchers, settings: &Settings) -> Option<usize> {
+    if settings.il_mode() || settings.single_mission().is_some() {
+        return None;
+    }
+
+    match timer::state() {
+        TimerState::Running | TimerState::Paused => {
+            Some(watchers.run.missions_completed + settings.first_mission())
+        }
+        _ if watchers.in_prologue => Some(0),
+        _ => None,
+    }
+}
+
+/// Writes the human-readable name of a level into `buf`, for the "Level" variable.
+///
+/// Codes that aren't in [`MISSIONS`] are named after `expected`, the mission a run is
+/// expected to be on, as long as it's one whose code hasn't been recorded either.
+/// Otherwise they come out as "Unknown (xx)", so new codes show up as they're
+/// played. Garbage that doesn't decode comes out empty.
+fn level_name<'a>(
+    level: &ArrayCString<2>,
+    expected: Option<usize>,
+    buf: &'a mut [u8; 12],
+) -> &'a str {
+    let Some(code) = level_code(level) else {
+        return "";
+    };
+    if let Some(index) = mission_index(level) {
+        return MISSIONS[index].name;
+    }
+    let expected = expected
+        .and_then(|index| MISSIONS.get(index))
+        .filter(|mission| mission.code.is_none());
+    match (code, expected) {
+        ("", _) => "",
+        (MENU, _) => "Main Menu",
+        (TUTORIAL, _) => "Tutorial",
+        (_, Some(mission)) => mission.name,
+        _ => {
+            *buf = *b"Unknown (  )";
+            buf[9..9 + code.len()].copy_from_slice(code.as_bytes());
+            let len = match code.len() {
+                1 => {
+                    buf[10] = b')';
+                    11
+                }
+                _ => 12,
+            };
+            core::str::from_utf8(&buf[..len]).unwrap_or_default()
+        }
+    }
+}
+
+/// The game time to send to the timer with the computed timing method, which is the
+/// time counted outside of loads.
+///
+/// In that case [`is_loading`] keeps game time paused for the whole run, so LiveSplit
+/// never advances it on its own and `timer::set_game_time` is the only thing moving it.
+/// Load removal through `pause_game_time` and `resume_game_time` only applies to the
+/// load removal timing method, and the two never mix within a run.
+fn game_time(state: &GameState, settings: &Settings) -> Option<Duration> {
+    if state.run.final_split_done || !settings.load_removal {
+        return None;
+    }
+
+    match settings.timing_method() {
+        TimingMethod::Computed => Some(state.run.loadless_time),
+        TimingMethod::LoadRemoval => None,
+    }
+}
+
+fn reset(state: &GameState, settings: &Settings) -> bool {
+    // A run that got started by selecting the tutorial rather than a New Game was never
+    // meant to start, so it gets thrown away whatever the reset settings
+    if !settings.il_mode()
+        && settings.single_mission().is_none()
+        && !settings.include_tutorial
+        && tutorial_started(state)
+    {
+        return true;
+    }
+
+    if settings.practice_mode {
+        return false;
+    }
+
+    // The transition the run started on may still flicker through something that
+    // looks like a reset, so nothing resets for half a second after the start
+    if (state.run.ticks_since_start as f64) < 0.5 * state.tick_rate {
+        return false;
+    }
+
+    // Starting a New Game over a run that never got reset. The start action runs right
+    // after the reset on the very same tick, so the new run starts off that same change.
+    if settings.reset_on_new_game && !settings.il_mode() && new_game_started(state) {
+        return !state.run.awaiting_new_game;
+    }
+
+    // Leaving the tutorial goes through the main menu on the way to the New Game, and
+    // the tutorial split fires on that very return to the menu. So does the final split
+    // when it's timed to the end of the finale.
+    if state.run.awaiting_new_game
+        || settings.include_tutorial && is_level(&state.level.old, TUTORIAL)
+        || settings.final_split_mode == FinalSplitMode::LevelEnd
+            && !state.run.final_split_done
+            && is_level(&state.level.old, FINALE)
+    {
+        return false;
+    }
+
+    // Returning to the menu right after a split must not race with it, so the reset is
+    // held back for a moment after each split, counted in ticks at the current tick rate
+    let in_grace = state
+        .run
+        .ticks_since_split
+        .is_some_and(|ticks| (ticks as f64) < settings.reset_grace.seconds() * state.tick_rate);
+
+    // The start byte can't be relied on here, as a campaign continued from a save never
+    // sets it. The main menu reads as a load itself, with both the load and splash bytes
+    // at 1, so nothing else is required of them.
+    (settings.reset_on_main_menu || settings.menu_behavior == MenuBehavior::Reset)
+        && settings.menu_behavior != MenuBehavior::Pause
+        && !in_grace
+        && returned_to_menu(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values read on a tick: the start, load and splash bytes, the level code,
+    /// the bullet cam, the objective counter and the mission complete flag.
+    type Tick = (u8, u8, u8, &'static str, u8, u8, u8);
+
+    /// What the splitter asks of the timer.
+    #[derive(Debug, PartialEq, Eq)]
+    enum Event {
+        Start,
+        Split(SplitKind),
+        Reset,
+        Pause,
+        Resume,
+    }
+
+    /// Builds a level code buffer out of its raw bytes.
+    fn level(bytes: [u8; 2]) -> ArrayCString<2> {
+        // SAFETY: An `ArrayCString` is nothing but its bytes, which get read straight
+        // out of the game's memory, so any bytes make up a valid one.
+        unsafe { core::mem::transmute::<[u8; 2], ArrayCString<2>>(bytes) }
+    }
+
+    /// Builds a level code out of up to two ASCII characters.
+    fn code(code: &str) -> ArrayCString<2> {
+        let mut bytes = [0; 2];
+        bytes[..code.len()].copy_from_slice(code.as_bytes());
+        level(bytes)
+    }
+
+    /// The snapshot of the game after reading each set of values once, outside of any run.
+    fn snapshot(ticks: &[Tick]) -> GameState {
+        let mut watchers = Watchers {
+            tick_rate: 120.0,
+            ..Default::default()
+        };
+        for &tick in ticks {
+            update_watchers(&mut watchers, values(tick));
+        }
+        GameState::new(&watchers).unwrap()
+    }
+
+    fn values((start, load, splash, level, bullet_cam, objective, mc): Tick) -> Values {
+        Values {
+            start: Some(start),
+            load: Some(load),
+            splash: Some(splash),
+            level: Some(code(level)),
+            bullet_cam: Some(bullet_cam),
+            objective: Some(objective),
+            mc: Some(mc),
+        }
+    }
+
+    /// The settings a new layout starts out with.
+    fn settings() -> Settings {
+        Settings {
+            _general: Title,
+            start_mode: StartMode::NewGame,
+            start_offset: StartOffset::None,
+            reset_on_main_menu: true,
+            menu_behavior: MenuBehavior::Nothing,
+            reset_on_new_game: true,
+            reset_grace: ResetGrace::OneSecond,
+            load_removal: true,
+            timing_method: TimingMethod::LoadRemoval,
+            game_time_update: GameTimeUpdate::Live,
+            remove_menu_time: true,
+            _full_game_splits: Title,
+            include_prologue: true,
+            include_tutorial: false,
+            follow_segments: false,
+            split_prologue: true,
+            split_mission_1: true,
+            split_mission_2: true,
+            split_mission_3: true,
+            split_mission_4: true,
+            split_mission_5: true,
+            split_mission_6: true,
+            split_mission_7: true,
+            split_mission_8: true,
+            split_mission_9: true,
+            split_on_final_kill: true,
+            final_split_mode: FinalSplitMode::BulletCamKill,
+            split_finale_cutscene: false,
+            split_timing: SplitTiming::OnMissionEnd,
+            objective_splits: false,
+            _single_mission: Title,
+            single_mission: SingleMission::Off,
+            _il_mode: Title,
+            individual_level: false,
+            il_start_mode: IlStartMode::Splash,
+            _bullet_cam: Title,
+            split_on_bullet_cam: false,
+            bullet_cam_target: BulletCamTarget::Unlimited,
+            _advanced: Title,
+            practice_mode: false,
+            reset_on_dead_reads: false,
+            debounce_loads: true,
+            min_load_ms: MinLoad::Off,
+            slow_pc_mode: false,
+            adaptive_tick_rate: false,
+            debug_variables: false,
+            missions_counted: MissionsCounted::Split,
+            glitch_filter: false,
+            verbose_logging: false,
+            offset_table: OffsetTable::Auto,
+        }
+    }
+
+    /// Stands in for LiveSplit's timer, recording what gets asked of it.
+    struct Recorder {
+        state: TimerState,
+        /// Number of segments in the layout, the last of which ends the run
+        segments: usize,
+        splits: usize,
+        game_time_paused: bool,
+        events: Vec<Event>,
+        /// Every game time sent to the timer
+        game_times: Vec<Duration>,
+    }
+
+    impl Timer for Recorder {
+        fn state(&self) -> TimerState {
+            self.state
+        }
+
+        fn start(&mut self) {
+            self.state = TimerState::Running;
+            self.splits = 0;
+            // LiveSplit runs game time as soon as the timer starts
+            self.game_time_paused = false;
+            self.events.push(Event::Start);
+        }
+
+        fn split(&mut self, kind: SplitKind) {
+            self.splits += 1;
+            if self.splits == self.segments {
+                self.state = TimerState::Ended;
+            }
+            self.events.push(Event::Split(kind));
+        }
+
+        fn reset(&mut self) {
+            self.state = TimerState::NotRunning;
+            self.events.push(Event::Reset);
+        }
+
+        // Pausing game time that's already paused does nothing, so only
+        // the changes get recorded
+        fn pause_game_time(&mut self) {
+            if !self.game_time_paused {
+                self.game_time_paused = true;
+                self.events.push(Event::Pause);
+            }
+        }
+
+        fn resume_game_time(&mut self) {
+            if self.game_time_paused {
+                self.game_time_paused = false;
+                self.events.push(Event::Resume);
+            }
+        }
+
+        fn set_game_time(&mut self, time: Duration) {
+            self.game_times.push(time);
+        }
+    }
+
+    /// Replays recorded values through the splitting logic, the same way `main` runs it
+    /// on every tick, against a timer that records what gets asked of it.
+    struct Replay {
+        settings: Settings,
+        watchers: Watchers,
+        timer: Recorder,
+    }
+
+    impl Replay {
+        /// Sets up a replay against a layout with more segments than the run can split.
+        fn new(settings: Settings) -> Self {
+            Self {
+                settings,
+                watchers: Watchers {
+                    tick_rate: 120.0,
+                    ..Default::default()
+                },
+                timer: Recorder {
+                    state: TimerState::NotRunning,
+                    segments: usize::MAX,
+                    splits: 0,
+                    game_time_paused: false,
+                    events: Vec::new(),
+                    game_times: Vec::new(),
+                },
+            }
+        }
+
+        /// Sets the number of segments in the layout, the last of which ends the run.
+        fn segments(mut self, segments: usize) -> Self {
+            self.timer.segments = segments;
+            self
+        }
+
+        /// Replays each set of values for the given number of ticks.
+        fn play(&mut self, ticks: &[(u32, Tick)]) -> &mut Self {
+            for &(count, tick) in ticks {
+                for _ in 0..count {
+                    self.tick(values(tick));
+                }
+            }
+            self
+        }
+
+        /// Replays each set of values for the given number of ticks, and returns
+        /// everything that got asked of the timer.
+        fn run(mut self, ticks: &[(u32, Tick)]) -> Vec<Event> {
+            self.play(ticks);
+            self.timer.events
+        }
+
+        /// The splits asked of the timer so far.
+        #[cfg(not(feature = "il-only"))]
+        fn splits(&self) -> Vec<SplitKind> {
+            self.timer
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Split(kind) => Some(*kind),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        fn tick(&mut self, values: Values) {
+            update_watchers(&mut self.watchers, values);
+            if reads_failing(&mut self.timer, &mut self.watchers, &self.settings) {
+                self.watchers.failed_ticks = 0;
+            }
+            let elapsed = Duration::seconds_f64(1.0 / self.watchers.tick_rate);
+            update_timer(&mut self.timer, &mut self.watchers, &self.settings, elapsed);
+        }
+    }
+
+    /// A New Game through the Prologue and one mission, up to the final kill in the
+    /// Brandenburg Gate finale and the credits. The codes of the missions played before
+    /// the finale haven't been recorded, so made up ones stand in for them.
+    #[cfg(not(feature = "il-only"))]
+    const FULL_GAME: &[(u32, Tick)] = &[
+        (60, (0, 1, 1, "nu", 0, 0, 0)),
+        (30, (1, 1, 1, "nu", 0, 0, 0)),
+        (120, (1, 1, 1, "Pr", 0, 0, 0)),
+        (600, (1, 0, 0, "Pr", 0, 0, 0)),
+        (30, (1, 0, 0, "Pr", 0, 1, 1)),
+        (30, (1, 1, 1, "Pr", 0, 0, 1)),
+        (120, (1, 1, 1, "M1", 0, 0, 0)),
+        (600, (1, 0, 0, "M1", 0, 0, 0)),
+        (30, (1, 0, 0, "M1", 0, 1, 1)),
+        (30, (1, 1, 1, "M1", 0, 0, 1)),
+        (120, (1, 1, 1, "Br", 0, 0, 0)),
+        (600, (1, 0, 0, "Br", 0, 0, 0)),
+        (300, (1, 0, 0, "Br", 0, 1, 0)),
+        (300, (1, 0, 0, "Br", 0, 2, 0)),
+        (300, (1, 0, 0, "Br", 0, 3, 0)),
+        (60, (1, 0, 0, "Br", 1, 3, 0)),
+        (120, (1, 0, 0, "Br", 0, 3, 1)),
+        (120, (0, 1, 1, "nu", 0, 0, 0)),
+    ];
+
+    /// A saved campaign continued from the main menu in Mission 3, through to Mission 4
+    /// and back to the main menu. The start byte is never set along the way. The codes
+    /// of both missions are made up.
+    #[cfg(not(feature = "il-only"))]
+    const LOAD_GAME: &[(u32, Tick)] = &[
+        (60, (0, 1, 1, "nu", 0, 0, 0)),
+        (120, (0, 1, 1, "M3", 0, 0, 0)),
+        (600, (0, 0, 0, "M3", 0, 0, 0)),
+        (30, (0, 0, 0, "M3", 0, 1, 1)),
+        (30, (0, 1, 1, "M3", 0, 0, 1)),
+        (120, (0, 1, 1, "M4", 0, 0, 0)),
+        (600, (0, 0, 0, "M4", 0, 0, 0)),
+        (60, (0, 1, 1, "nu", 0, 0, 0)),
+    ];
+
+    /// A single mission picked out of the main menu, from its splash screen to the
+    /// mission complete flag.
+    const INDIVIDUAL_LEVEL: &[(u32, Tick)] = &[
+        (60, (0, 1, 1, "nu", 0, 0, 0)),
+        (30, (1, 1, 0, "M3", 0, 0, 0)),
+        (60, (1, 1, 1, "M3", 0, 0, 0)),
+        (600, (1, 0, 0, "M3", 0, 0, 0)),
+        (300, (1, 0, 0, "M3", 0, 1, 0)),
+        (60, (1, 0, 0, "M3", 0, 1, 1)),
+    ];
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn full_game_run() {
+        assert_eq!(
+            Replay::new(settings()).segments(3).run(FULL_GAME),
+            [
+                Event::Start,
+                Event::Pause,
+                Event::Resume,
+                Event::Pause,
+                Event::Split(SplitKind::LevelChange),
+                Event::Resume,
+                Event::Pause,
+                Event::Split(SplitKind::LevelChange),
+                Event::Resume,
+                Event::Split(SplitKind::FinalKill),
+                Event::Pause,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn load_game_run() {
+        let events = [
+            Event::Start,
+            Event::Pause,
+            Event::Resume,
+            Event::Pause,
+            Event::Split(SplitKind::LevelChange),
+            Event::Resume,
+            Event::Reset,
+        ];
+        let load_game = || Settings {
+            start_mode: StartMode::LoadGame,
+            ..settings()
+        };
+        assert_eq!(Replay::new(load_game()).run(LOAD_GAME), events);
+
+        // Menu time only covers the main menu itself
+        let kept = Replay::new(Settings {
+            remove_menu_time: false,
+            ..load_game()
+        })
+        .run(LOAD_GAME);
+        assert_eq!(kept[..6], events[..6]);
+
+        let paused = Replay::new(Settings {
+            menu_behavior: MenuBehavior::Pause,
+            ..load_game()
+        })
+        .run(LOAD_GAME);
+        assert_eq!(paused[..6], events[..6]);
+    }
+
+    #[test]
+    fn individual_level_run() {
+        let settings = Settings {
+            individual_level: true,
+            ..settings()
+        };
+        assert_eq!(
+            Replay::new(settings).segments(1).run(INDIVIDUAL_LEVEL),
+            [
+                Event::Start,
+                Event::Pause,
+                Event::Resume,
+                Event::Split(SplitKind::MissionComplete),
+                Event::Pause,
+            ]
+        );
+    }
+
+    #[test]
+    fn individual_level_starts_on_gameplay() {
+        let settings = Settings {
+            individual_level: true,
+            il_start_mode: IlStartMode::Gameplay,
+            ..settings()
+        };
+        assert_eq!(
+            Replay::new(settings).segments(1).run(INDIVIDUAL_LEVEL),
+            [
+                Event::Start,
+                Event::Pause,
+                Event::Resume,
+                Event::Split(SplitKind::MissionComplete),
+                Event::Pause,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn level_change_splits() {
+        let mut replay = Replay::new(settings());
+        replay.play(&FULL_GAME[..7]);
+        assert_eq!(replay.splits(), [SplitKind::LevelChange]);
+        assert_eq!(replay.watchers.run.missions_completed, 1);
+        assert_eq!(replay.watchers.run.last_split_level, Some(code("Pr")));
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn bullet_cam_kill_in_the_finale_splits() {
+        let state = snapshot(&[(1, 0, 0, "Br", 0, 3, 0), (1, 0, 0, "Br", 1, 3, 0)]);
+        assert_eq!(split(&state, &settings()), Some(SplitKind::FinalKill));
+
+        // Not before the last objective is done
+        let state = snapshot(&[(1, 0, 0, "Br", 0, 2, 0), (1, 0, 0, "Br", 1, 2, 0)]);
+        assert_eq!(split(&state, &settings()), None);
+    }
+
+    #[test]
+    fn bullet_cam_outside_of_the_finale_doesnt_split() {
+        let state = snapshot(&[(1, 0, 0, "M5", 0, 3, 0), (1, 0, 0, "M5", 1, 3, 0)]);
+        assert_eq!(split(&state, &settings()), None);
+        assert!(!final_kill(&state, FinalSplitMode::BulletCamKill));
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn nothing_splits_after_the_final_split() {
+        let mut replay = Replay::new(settings());
+        replay.play(&FULL_GAME[..16]);
+        assert_eq!(replay.splits().last(), Some(&SplitKind::FinalKill));
+        assert!(replay.watchers.run.final_split_done);
+
+        let after = [
+            // Another bullet cam kill
+            [(1, 0, 0, "Br", 0, 3, 0), (1, 0, 0, "Br", 1, 3, 0)],
+            // The mission complete flag
+            [(1, 0, 0, "Br", 0, 3, 0), (1, 0, 0, "Br", 0, 3, 1)],
+            // Another mission loaded after the credits
+            [(1, 1, 1, "Br", 0, 0, 0), (1, 1, 1, "M1", 0, 0, 0)],
+        ];
+        for ticks in after {
+            let state = GameState {
+                run: replay.watchers.run,
+                ..snapshot(&ticks)
+            };
+            assert_eq!(split(&state, &replay.settings), None);
+            assert_eq!(is_loading(&state, &replay.settings), None);
+        }
+    }
+
+    #[test]
+    fn the_tutorial_is_never_a_mission() {
+        assert!(!is_real_mission(&code("Tu")));
+        assert!(!mission_ended(&snapshot(&[
+            (1, 1, 1, "M1", 0, 0, 0),
+            (1, 1, 1, "Tu", 0, 0, 0),
+        ])));
+        assert_eq!(mission_index(&code("Tu")), None);
+
+        let il = Settings {
+            individual_level: true,
+            ..settings()
+        };
+        let tutorial = [
+            (60, (0, 1, 1, "nu", 0, 0, 0)),
+            (30, (1, 1, 0, "Tu", 0, 0, 0)),
+            (60, (1, 1, 1, "Tu", 0, 0, 0)),
+            (600, (1, 0, 0, "Tu", 0, 0, 0)),
+            (60, (1, 0, 0, "Tu", 0, 1, 1)),
+        ];
+        assert_eq!(Replay::new(il).run(&tutorial), []);
+
+        // Selecting it looks just like a New Game until it loads
+        #[cfg(not(feature = "il-only"))]
+        assert_eq!(
+            Replay::new(settings()).run(&[
+                (60, (0, 1, 1, "nu", 0, 0, 0)),
+                (30, (1, 1, 1, "nu", 0, 0, 0)),
+                (120, (1, 1, 1, "Tu", 0, 0, 0)),
+                (600, (1, 0, 0, "Tu", 0, 0, 0)),
+                (60, (0, 1, 1, "nu", 0, 0, 0)),
+            ]),
+            [Event::Start, Event::Pause, Event::Reset]
+        );
+    }
+
+    #[test]
+    fn attaching_mid_mission_doesnt_start() {
+        let mission = [
+            (600, (1, 0, 0, "M1", 0, 0, 0)),
+            (30, (1, 1, 1, "M1", 0, 0, 1)),
+            (120, (1, 1, 1, "M2", 0, 0, 0)),
+            (600, (1, 0, 0, "M2", 0, 0, 0)),
+        ];
+        assert_eq!(Replay::new(settings()).run(&mission), []);
+
+        let settings = Settings {
+            individual_level: true,
+            il_start_mode: IlStartMode::Gameplay,
+            ..settings()
+        };
+        assert_eq!(Replay::new(settings).run(&mission), []);
+    }
+
+    #[test]
+    fn the_first_read_after_attaching_changes_nothing() {
+        let state = snapshot(&[(1, 1, 1, "M1", 0, 0, 0)]);
+        assert!(!state.start_byte.changed());
+        assert!(!state.level.changed());
+        assert!(!state.level_settled);
+        assert!(!new_game_started(&state));
+        assert!(!mission_ended(&state));
+
+        // Even if the previous attach left off somewhere else entirely
+        let state = snapshot(&[(0, 1, 1, "nu", 0, 0, 0)]);
+        assert!(!state.start_settled);
+        assert!(!new_game_started(&GameState {
+            start_byte: Pair { old: 0, current: 1 },
+            ..state
+        }));
+    }
+
+    #[test]
+    fn failed_reads_keep_the_last_value() {
+        let mut watchers = Watchers {
+            tick_rate: 120.0,
+            ..Default::default()
+        };
+        update_watchers(&mut watchers, values((1, 0, 0, "M1", 0, 0, 0)));
+        update_watchers(&mut watchers, values((1, 0, 0, "M1", 0, 0, 0)));
+
+        update_watchers(&mut watchers, Values::default());
+        let state = GameState::new(&watchers).unwrap();
+        assert_eq!(watchers.failed_ticks, 1);
+        assert_eq!(state.level.current, code("M1"));
+        assert!(!state.level.changed());
+        assert!(!state.start_byte.changed());
+
+        update_watchers(&mut watchers, values((1, 0, 0, "M1", 0, 0, 0)));
+        let state = GameState::new(&watchers).unwrap();
+        assert_eq!(watchers.failed_ticks, 0);
+        assert!(!state.level.changed());
+        assert!(!state.start_byte.changed());
+    }
+
+    #[test]
+    fn debounce_hides_flickering_loads() {
+        let flickering: Vec<_> = INDIVIDUAL_LEVEL[..4]
+            .iter()
+            .copied()
+            .chain(
+                (0..5).flat_map(|_| [(1, (1, 1, 1, "M3", 0, 0, 0)), (1, (1, 0, 0, "M3", 0, 0, 0))]),
+            )
+            .chain([(60, (1, 0, 0, "M3", 0, 1, 1))])
+            .collect();
+        let debounced = || Settings {
+            individual_level: true,
+            ..settings()
+        };
+        assert_eq!(
+            Replay::new(debounced()).segments(1).run(&flickering),
+            [
+                Event::Start,
+                Event::Pause,
+                Event::Resume,
+                Event::Split(SplitKind::MissionComplete),
+                Event::Pause,
+            ]
+        );
+
+        let events = Replay::new(Settings {
+            debounce_loads: false,
+            ..debounced()
+        })
+        .segments(1)
+        .run(&flickering);
+        // Each of the 5 flickers pauses game time, on top of the start and the final split
+        assert_eq!(events.iter().filter(|&e| *e == Event::Pause).count(), 7);
+    }
+
+    #[test]
+    fn phases() {
+        let phase = |tick| Phase::of(&snapshot(&[tick])).name();
+        assert_eq!(phase((0, 1, 1, "nu", 0, 0, 0)), "Menu");
+        assert_eq!(phase((1, 1, 1, "M1", 0, 0, 0)), "Loading");
+        assert_eq!(phase((1, 0, 0, "M1", 0, 0, 0)), "In Mission");
+        assert_eq!(phase((1, 0, 0, "M1", 0, 1, 1)), "Mission Complete");
+        assert_eq!(phase((1, 0, 0, "Br", 0, 0, 0)), "Finale");
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn every_split_timing_splits_once_per_mission() {
+        for (split_timing, kind) in [
+            (SplitTiming::OnMissionEnd, SplitKind::LevelChange),
+            (SplitTiming::OnMissionStart, SplitKind::MissionStart),
+        ] {
+            let mut replay = Replay::new(Settings {
+                split_timing,
+                ..settings()
+            })
+            .segments(3);
+            replay.play(FULL_GAME);
+            assert_eq!(replay.splits(), [kind, kind, SplitKind::FinalKill]);
+            assert_eq!(replay.watchers.run.missions_completed, 2);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn a_reset_by_hand_clears_the_run() {
+        let mut replay = Replay::new(settings()).segments(3);
+        replay.play(FULL_GAME);
+        assert!(replay.watchers.run.final_split_done);
+
+        replay.timer.reset();
+        replay.play(&FULL_GAME[..1]);
+        assert!(!replay.watchers.run.final_split_done);
+        assert_eq!(replay.watchers.run.missions_completed, 0);
+        assert_eq!(replay.watchers.run.load_count, 0);
+
+        // A run started by hand splits like any other
+        replay.timer.start();
+        replay.timer.events.clear();
+        replay.play(&FULL_GAME[1..]);
+        assert_eq!(
+            replay.splits(),
+            [
+                SplitKind::LevelChange,
+                SplitKind::LevelChange,
+                SplitKind::FinalKill
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn computed_game_time_counts_the_time_outside_of_loads() {
+        let mut replay = Replay::new(Settings {
+            timing_method: TimingMethod::Computed,
+            ..settings()
+        })
+        .segments(3);
+        replay.play(FULL_GAME);
+        assert_eq!(replay.splits().last(), Some(&SplitKind::FinalKill));
+        assert!(replay.timer.game_times.windows(2).all(|x| x[0] <= x[1]));
+
+        // The Prologue and Mission 1 each get played for 630 ticks, and the finale
+        // for 1500 before the tick of the final kill ends the run
+        let tick = Duration::seconds_f64(1.0 / 120.0);
+        assert_eq!(replay.watchers.run.loadless_time, tick * 2760u32);
+        assert_eq!(replay.watchers.run.load_count, 3);
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn game_time_gets_sent_as_often_as_asked() {
+        for (game_time_update, sent) in
+            [(GameTimeUpdate::OnSplit, 3), (GameTimeUpdate::FinalOnly, 1)]
+        {
+            let mut replay = Replay::new(Settings {
+                timing_method: TimingMethod::Computed,
+                game_time_update,
+                ..settings()
+            })
+            .segments(3);
+            replay.play(FULL_GAME);
+            assert_eq!(replay.timer.game_times.len(), sent);
+        }
+    }
+
+    #[test]
+    fn short_loads_get_credited_back() {
+        let play = |flicker| {
+            let ticks: Vec<_> = INDIVIDUAL_LEVEL[..4]
+                .iter()
+                .copied()
+                .chain((0..3).flat_map(|_| [(1, flicker), (120, (1, 0, 0, "M3", 0, 0, 0))]))
+                .chain([(60, (1, 0, 0, "M3", 0, 1, 1))])
+                .collect();
+            // With the default timing method, which the minimum load duration overrides
+            let mut replay = Replay::new(Settings {
+                individual_level: true,
+                min_load_ms: MinLoad::Ms100,
+                ..settings()
+            })
+            .segments(1);
+            replay.play(&ticks);
+            replay
+        };
+        let flickering = play((1, 1, 1, "M3", 0, 0, 0));
+        let steady = play((1, 0, 0, "M3", 0, 0, 0));
+        assert_eq!(
+            flickering.watchers.run.loadless_time,
+            steady.watchers.run.loadless_time
+        );
+        assert_eq!(
+            flickering.watchers.run.load_count,
+            steady.watchers.run.load_count
+        );
+        assert!(!steady.timer.game_times.is_empty());
+        assert_eq!(
+            flickering.timer.game_times.last(),
+            steady.timer.game_times.last()
+        );
+        assert_eq!(flickering.timer.events, steady.timer.events);
+    }
+
+    #[test]
+    fn level_codes() {
+        assert_eq!(level_code(&code("M1")), Some("M1"));
+        assert_eq!(level_code(&level(*b"M\0")), Some("M"));
+        assert_eq!(level_code(&level([b'B', 0xff])), None);
+        assert_eq!(level_code(&level(*b"B-")), None);
+        assert_eq!(level_code(&level([0, b'r'])), Some(""));
+        assert!(!is_level(&level(*b"B\0"), FINALE));
+        assert!(!is_real_mission(&level([0, b'r'])));
+        assert!(!is_real_mission(&level([b'B', 0xff])));
+    }
+
+    #[test]
+    fn loads_need_both_bytes() {
+        assert!(loading(&snapshot(&[(1, 1, 1, "M1", 0, 0, 0)])));
+        assert!(!loading(&snapshot(&[(1, 1, 0, "M1", 0, 0, 0)])));
+        assert!(!loading(&snapshot(&[(1, 0, 1, "M1", 0, 0, 0)])));
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn starting_after_the_prologue() {
+        let settings = Settings {
+            include_prologue: false,
+            ..settings()
+        };
+        assert_eq!(
+            Replay::new(settings).segments(2).run(FULL_GAME),
+            [
+                Event::Start,
+                Event::Pause,
+                Event::Resume,
+                Event::Pause,
+                Event::Split(SplitKind::LevelChange),
+                Event::Resume,
+                Event::Split(SplitKind::FinalKill),
+                Event::Pause,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn level_end_splits_on_the_return_to_the_menu() {
+        let settings = Settings {
+            final_split_mode: FinalSplitMode::LevelEnd,
+            ..settings()
+        };
+        let events = Replay::new(settings).segments(3).run(FULL_GAME);
+        assert_eq!(
+            events[events.len() - 2..],
+            [Event::Split(SplitKind::FinalKill), Event::Pause]
+        );
+        assert!(!events.contains(&Event::Reset));
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn without_load_removal() {
+        let settings = Settings {
+            load_removal: false,
+            ..settings()
+        };
+        assert_eq!(
+            Replay::new(settings).segments(3).run(FULL_GAME),
+            [
+                Event::Start,
+                Event::Split(SplitKind::LevelChange),
+                Event::Split(SplitKind::LevelChange),
+                Event::Split(SplitKind::FinalKill),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn back_to_back_missions_split_twice() {
+        let mut replay = Replay::new(settings());
+        replay.play(&FULL_GAME[..6]).play(&[
+            (1, (1, 1, 1, "M1", 0, 0, 0)),
+            (120, (1, 1, 1, "M2", 0, 0, 0)),
+        ]);
+        assert_eq!(
+            replay.splits(),
+            [SplitKind::LevelChange, SplitKind::LevelChange]
+        );
+        assert_eq!(replay.watchers.run.missions_completed, 2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn no_reset_right_after_the_start() {
+        let mut ticks = vec![
+            (60, (0, 1, 1, "nu", 0, 0, 0)),
+            (1, (1, 1, 1, "nu", 0, 0, 0)),
+            (1, (1, 1, 1, "Pr", 0, 0, 0)),
+            (1, (1, 1, 1, "nu", 0, 0, 0)),
+            (120, (1, 1, 1, "Pr", 0, 0, 0)),
+        ];
+        assert_eq!(
+            Replay::new(settings()).run(&ticks),
+            [Event::Start, Event::Pause]
+        );
+
+        ticks.push((1, (1, 1, 1, "nu", 0, 0, 0)));
+        assert_eq!(
+            Replay::new(settings()).run(&ticks),
+            [Event::Start, Event::Pause, Event::Reset]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn the_tutorial_splits_ahead_of_a_new_game() {
+        assert!(tutorial_started(&snapshot(&[
+            (1, 1, 1, "nu", 0, 0, 0),
+            (1, 1, 1, "Tu", 0, 0, 0),
+        ])));
+
+        let left = snapshot(&[(1, 1, 1, "Tu", 0, 0, 0), (1, 1, 1, "nu", 0, 0, 0)]);
+        let included = Settings {
+            include_tutorial: true,
+            ..settings()
+        };
+        assert_eq!(full_game_split(&left, &included), Some(SplitKind::Tutorial));
+        assert_eq!(full_game_split(&left, &settings()), None);
+
+        let ticks: Vec<_> = [
+            (60, (0, 1, 1, "nu", 0, 0, 0)),
+            (30, (1, 1, 1, "nu", 0, 0, 0)),
+            (120, (1, 1, 1, "Tu", 0, 0, 0)),
+            (600, (1, 0, 0, "Tu", 0, 0, 0)),
+            (30, (1, 1, 1, "Tu", 0, 0, 0)),
+            (30, (1, 1, 1, "nu", 0, 0, 0)),
+            (60, (0, 1, 1, "nu", 0, 0, 0)),
+        ]
+        .into_iter()
+        .chain(FULL_GAME[1..7].iter().copied())
+        .collect();
+        let mut replay = Replay::new(included);
+        replay.play(&ticks);
+        assert_eq!(
+            replay.splits(),
+            [SplitKind::Tutorial, SplitKind::LevelChange]
+        );
+        assert!(!replay.timer.events.contains(&Event::Reset));
+        assert_eq!(
+            replay
+                .timer
+                .events
+                .iter()
+                .filter(|&e| *e == Event::Start)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "il-only"))]
+    fn the_glitch_filter_ignores_a_level_read_wrong_for_a_tick() {
+        let ticks = [
+            (60, (0, 1, 1, "nu", 0, 0, 0)),
+            (30, (1, 1, 1, "nu", 0, 0, 0)),
+            (120, (1, 1, 1, "Pr", 0, 0, 0)),
+            (300, (1, 0, 0, "Pr", 0, 0, 0)),
+            (1, (1, 0, 0, "M9", 0, 0, 0)),
+            (300, (1, 0, 0, "Pr", 0, 0, 0)),
+        ];
+        let mut replay = Replay::new(Settings {
+            glitch_filter: true,
+            ..settings()
+        });
+        replay.play(&ticks);
+        assert_eq!(replay.splits(), []);
+        assert_eq!(replay.watchers.run.missions_completed, 0);
+
+        let mut replay = Replay::new(settings());
+        replay.play(&ticks);
+        assert_eq!(replay.splits().len(), 2);
+        assert_eq!(replay.watchers.run.missions_completed, 2);
+    }
 }